@@ -0,0 +1,72 @@
+import assert from 'node:assert/strict'
+import { test } from 'node:test'
+
+import codeSplitter from '../index.js'
+
+const { CharSplitter } = codeSplitter
+
+const PYTHON_INPUT = `# Größen berechnen 📐
+def area(w, h):
+    return w * h
+
+
+def perimeter(w, h):
+    return 2 * (w + h)
+`
+
+const split = (code) => new CharSplitter('python', 40).splitSync(code)
+
+test('offsets of a string', () => {
+  const chunks = split(PYTHON_INPUT)
+  const bytes = Buffer.from(PYTHON_INPUT)
+
+  assert.ok(chunks.length > 1)
+  assert.deepEqual(chunks, split(bytes))
+  for (const chunk of chunks) {
+    assert.equal(PYTHON_INPUT.slice(chunk.startIndex, chunk.endIndex), chunk.text)
+    assert.equal(bytes.subarray(chunk.startByte, chunk.endByte).toString(), chunk.text)
+  }
+})
+
+test('columns', () => {
+  const lines = PYTHON_INPUT.split('\n')
+  for (const chunk of split(PYTHON_INPUT)) {
+    const textLines = chunk.text.split('\n')
+
+    assert.ok(lines[chunk.start].slice(chunk.startColumn).startsWith(textLines[0]))
+    assert.ok(lines[chunk.end].slice(0, chunk.endColumn).endsWith(textLines.at(-1)))
+  }
+})
+
+test('non-ASCII offsets', () => {
+  const [first] = split(PYTHON_INPUT)
+
+  assert.ok(first.text.startsWith('# Größen berechnen 📐'))
+  // "ö" and "ß" take two bytes but one code unit, "📐" four bytes but two code units
+  assert.ok(first.endByte > first.endIndex)
+})
+
+test('chunk fields', () => {
+  const [chunk] = split('x = 1')
+
+  assert.deepEqual(Object.keys(chunk).sort(), [
+    'end',
+    'endByte',
+    'endColumn',
+    'endIndex',
+    'size',
+    'start',
+    'startByte',
+    'startColumn',
+    'startIndex',
+    'subtree',
+    'text',
+  ])
+  assert.equal(chunk.text, 'x = 1')
+  assert.equal(chunk.tokenizer, undefined)
+})
+
+test('empty code', async () => {
+  assert.deepEqual(split(''), [])
+  assert.deepEqual(await new CharSplitter('python', 40).split(''), [])
+})
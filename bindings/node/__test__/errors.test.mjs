@@ -0,0 +1,43 @@
+import assert from 'node:assert/strict'
+import { test } from 'node:test'
+
+import codeSplitter from '../index.js'
+
+const { CharSplitter, HuggingfaceSplitter, Splitter, TiktokenSplitter } = codeSplitter
+
+test('unsupported language', () => {
+  assert.throws(() => new CharSplitter('klingon', 128), {
+    code: 'LanguageError',
+    message: 'Unsupported language: klingon',
+  })
+  assert.throws(() => new Splitter('klingon', 128, (text) => text.length), {
+    code: 'LanguageError',
+  })
+})
+
+test('sizer error', () => {
+  assert.throws(() => new HuggingfaceSplitter('markdown', 128, 'does-not-exist/tokenizer'), {
+    code: 'SizerError',
+  })
+})
+
+test('unknown tiktoken encoding', () => {
+  assert.throws(() => new TiktokenSplitter('markdown', 128, { encoding: 'nope' }), {
+    code: 'InvalidArg',
+    message: 'Invalid argument: unknown tiktoken encoding nope',
+  })
+  assert.throws(() => new TiktokenSplitter('markdown', 128, { model: 'nope' }), {
+    code: 'InvalidArg',
+  })
+  assert.throws(
+    () => new TiktokenSplitter('markdown', 128, { encoding: 'cl100k_base', model: 'gpt-4o' }),
+    { code: 'InvalidArg' },
+  )
+})
+
+test('invalid code', () => {
+  const splitter = new CharSplitter('markdown', 128)
+
+  assert.throws(() => splitter.splitSync(42))
+  assert.throws(() => splitter.split(42))
+})
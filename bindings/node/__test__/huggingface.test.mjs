@@ -0,0 +1,56 @@
+import assert from 'node:assert/strict'
+import { readFileSync } from 'node:fs'
+import { test } from 'node:test'
+
+import codeSplitter from '../index.js'
+
+const { HuggingfaceSplitter } = codeSplitter
+
+// A small word-level tokenizer that adds [CLS] and [SEP] around every input
+const TOKENIZER_FILE = new URL('../../../tests/testdata/tokenizer.json', import.meta.url).pathname
+
+const MARKDOWN_INPUT = `# hello
+
+hello, world!
+
+hello world hello world
+`
+
+test('from file', async () => {
+  const splitter = HuggingfaceSplitter.fromFile('markdown', 4, TOKENIZER_FILE)
+  const chunks = await splitter.split(MARKDOWN_INPUT)
+
+  assert.deepEqual(
+    chunks.map((chunk) => chunk.size),
+    [2, 4, 4],
+  )
+  assert.ok(chunks.every((chunk) => chunk.tokenizer === undefined))
+})
+
+test('from buffer', () => {
+  const splitter = HuggingfaceSplitter.fromBuffer('markdown', 4, readFileSync(TOKENIZER_FILE))
+
+  assert.deepEqual(
+    splitter.splitSync(MARKDOWN_INPUT).map((chunk) => chunk.size),
+    [2, 4, 4],
+  )
+})
+
+test('add special tokens', () => {
+  const splitter = HuggingfaceSplitter.fromFile('markdown', 6, TOKENIZER_FILE, true)
+
+  // [CLS] and [SEP] count toward every chunk
+  assert.deepEqual(
+    splitter.splitSync(MARKDOWN_INPUT).map((chunk) => chunk.size),
+    [4, 6, 6],
+  )
+})
+
+test('invalid tokenizer', () => {
+  assert.throws(() => HuggingfaceSplitter.fromBuffer('markdown', 4, Buffer.from('{}')), {
+    code: 'SizerError',
+  })
+  assert.throws(() => HuggingfaceSplitter.fromFile('markdown', 4, 'does/not/exist.json'), {
+    code: 'SizerError',
+  })
+})
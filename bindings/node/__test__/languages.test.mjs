@@ -0,0 +1,39 @@
+import assert from 'node:assert/strict'
+import { readdirSync, readFileSync } from 'node:fs'
+import { extname, join } from 'node:path'
+import { test } from 'node:test'
+
+import codeSplitter from '../index.js'
+
+const { CharSplitter, languageFromExtension, languages } = codeSplitter
+
+// The samples of every bundled language, shared with the Python binding
+const TESTDATA = new URL('../../python/tests/testdata', import.meta.url).pathname
+
+test('languages', () => {
+  assert.equal(languages().length, 22)
+  assert.ok(languages().includes('python'))
+})
+
+test('every language splits its sample', async () => {
+  const files = readdirSync(TESTDATA)
+  assert.equal(files.length, languages().length)
+
+  for (const file of files) {
+    const language = languageFromExtension(extname(file))
+    const code = readFileSync(join(TESTDATA, file), 'utf8')
+    const chunks = await new CharSplitter(language, 128).split(code)
+
+    assert.ok(chunks.length > 0, file)
+    for (const chunk of chunks) {
+      assert.equal(code.slice(chunk.startIndex, chunk.endIndex), chunk.text, file)
+    }
+  }
+})
+
+test('language from extension', () => {
+  assert.equal(languageFromExtension('py'), 'python')
+  assert.equal(languageFromExtension('.rs'), 'rust')
+  assert.equal(languageFromExtension('TSX'), 'tsx')
+  assert.throws(() => languageFromExtension('klingon'), { code: 'LanguageError' })
+})
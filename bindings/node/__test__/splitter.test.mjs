@@ -0,0 +1,108 @@
+import assert from 'node:assert/strict'
+import { test } from 'node:test'
+
+import codeSplitter from '../index.js'
+
+const { CharSplitter, Splitter, TiktokenSplitter, WordSplitter } = codeSplitter
+
+const PYTHON_INPUT = `def add(a, b):
+    return a + b
+
+
+def sub(a, b):
+    return a - b
+
+
+class Calculator:
+    def mul(self, a, b):
+        return a * b
+`
+
+const vowels = (text) => [...text].filter((c) => 'aeiou'.includes(c)).length
+
+test('split resolves to the chunks of splitSync', async () => {
+  for (const splitter of [
+    new CharSplitter('python', 30),
+    new WordSplitter('python', 5),
+    new TiktokenSplitter('python', 10),
+  ]) {
+    const chunks = await splitter.split(PYTHON_INPUT)
+
+    assert.ok(chunks.length > 1)
+    assert.deepEqual(chunks, splitter.splitSync(PYTHON_INPUT))
+  }
+})
+
+test('split accepts a Buffer', async () => {
+  const splitter = new CharSplitter('python', 30)
+
+  assert.deepEqual(await splitter.split(Buffer.from(PYTHON_INPUT)), await splitter.split(PYTHON_INPUT))
+})
+
+test('split runs off the main thread', async () => {
+  const splitter = new CharSplitter('python', 30)
+  let resolved = false
+  const pending = splitter.split(PYTHON_INPUT.repeat(200)).then((chunks) => {
+    resolved = true
+    return chunks
+  })
+
+  assert.equal(resolved, false)
+  const results = await Promise.all([pending, splitter.split(PYTHON_INPUT)])
+  assert.equal(resolved, true)
+  assert.deepEqual(results[1], splitter.splitSync(PYTHON_INPUT))
+})
+
+test('tiktoken splitter', () => {
+  const splitter = new TiktokenSplitter('python', 10, { model: 'gpt-4o' })
+  const chunks = splitter.splitSync(PYTHON_INPUT)
+
+  assert.equal(splitter.encoding, 'o200k_base')
+  for (const chunk of chunks) {
+    assert.equal(chunk.tokenizer, 'o200k_base')
+    assert.ok(chunk.size <= 10)
+  }
+  assert.equal(new TiktokenSplitter('python', 10).encoding, 'cl100k_base')
+})
+
+test('function sizer', async () => {
+  // Measuring the length of the text matches the character splitter on ASCII code
+  const splitter = new Splitter('python', 30, (text) => text.length)
+  const expected = new CharSplitter('python', 30).splitSync(PYTHON_INPUT)
+
+  assert.deepEqual(splitter.splitSync(PYTHON_INPUT), expected)
+  assert.deepEqual(await splitter.split(PYTHON_INPUT), expected)
+})
+
+test('object sizer', async () => {
+  const splitter = new Splitter('python', 5, { size: vowels })
+
+  for (const chunk of await splitter.split(PYTHON_INPUT)) {
+    assert.equal(chunk.size, vowels(chunk.text))
+    assert.ok(chunk.size <= 5)
+  }
+})
+
+test('sizer exceptions propagate', async () => {
+  const error = new RangeError('quota exceeded')
+  const splitter = new Splitter('python', 30, () => {
+    throw error
+  })
+
+  assert.throws(() => splitter.splitSync(PYTHON_INPUT), (e) => e === error)
+  await assert.rejects(splitter.split(PYTHON_INPUT), (e) => e === error)
+})
+
+test('invalid sizes', async () => {
+  for (const size of [-1, 1.5, '1', NaN]) {
+    const splitter = new Splitter('python', 30, () => size)
+
+    assert.throws(() => splitter.splitSync(PYTHON_INPUT), TypeError)
+    await assert.rejects(splitter.split(PYTHON_INPUT), TypeError)
+  }
+})
+
+test('invalid sizer', () => {
+  assert.throws(() => new Splitter('python', 30, 42), TypeError)
+  assert.throws(() => new Splitter('python', 30, { size: 42 }), TypeError)
+})
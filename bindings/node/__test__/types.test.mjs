@@ -0,0 +1,45 @@
+import assert from 'node:assert/strict'
+import { readFileSync } from 'node:fs'
+import { test } from 'node:test'
+
+import codeSplitter from '../index.js'
+
+const TYPES = readFileSync(new URL('../index.d.ts', import.meta.url), 'utf8')
+
+// Get the members declared in the body of each exported class of the type definitions
+function declaredClasses() {
+  const classes = {}
+  for (const [, name, body] of TYPES.matchAll(/^export class (\w+) \{\n([\s\S]*?)^\}/gm)) {
+    const members = [...body.matchAll(/^ {2}(?:static |readonly )?(\w+)[(:]/gm)].map(([, member]) => member)
+    classes[name] = new Set(members.filter((member) => member !== 'constructor'))
+  }
+  return classes
+}
+
+test('types cover the exports', () => {
+  const functions = [...TYPES.matchAll(/^export function (\w+)/gm)].map(([, name]) => name)
+
+  assert.deepEqual(
+    Object.keys(codeSplitter).sort(),
+    [...functions, ...Object.keys(declaredClasses())].sort(),
+  )
+})
+
+test('types cover the members', () => {
+  for (const [name, members] of Object.entries(declaredClasses())) {
+    const cls = codeSplitter[name]
+    const runtime = [
+      ...Object.getOwnPropertyNames(cls).filter((member) => typeof cls[member] === 'function'),
+      ...Object.getOwnPropertyNames(cls.prototype).filter((member) => member !== 'constructor'),
+    ]
+
+    assert.deepEqual(new Set(runtime), members, name)
+  }
+})
+
+test('types cover the languages', () => {
+  const [, union] = TYPES.match(/^export type Language =\n((?: {2}\| '\w+'\n)+)/m)
+  const declared = [...union.matchAll(/'(\w+)'/g)].map(([, language]) => language)
+
+  assert.deepEqual(declared.sort(), codeSplitter.languages().sort())
+})
@@ -0,0 +1,168 @@
+/// <reference types="node" />
+
+/** Identifier of a language with a grammar bundled in the addon. */
+export type Language =
+  | 'bash'
+  | 'c'
+  | 'cpp'
+  | 'csharp'
+  | 'css'
+  | 'go'
+  | 'html'
+  | 'java'
+  | 'javascript'
+  | 'json'
+  | 'kotlin'
+  | 'markdown'
+  | 'php'
+  | 'python'
+  | 'ruby'
+  | 'rust'
+  | 'sql'
+  | 'swift'
+  | 'toml'
+  | 'typescript'
+  | 'tsx'
+  | 'yaml'
+
+/** Code to split: a string, or a `Buffer` holding the code in any encoding. */
+export type Code = string | Buffer
+
+/**
+ * A chunk of code. Offsets are given in bytes of the original code, and in UTF-16 code units
+ * of the code decoded with its encoding, so that `code.slice(startIndex, endIndex) === text`
+ * for a string input.
+ */
+export interface Chunk {
+  /** Subtree representation of the chunk. */
+  subtree: string
+  /** Row of the first line of the chunk, starting at 0. */
+  start: number
+  /** Row of the last line of the chunk. */
+  end: number
+  /** Byte offset of the start of the chunk. */
+  startByte: number
+  /** Byte offset of the end of the chunk, exclusive. */
+  endByte: number
+  /** String index of the start of the chunk. */
+  startIndex: number
+  /** String index of the end of the chunk, exclusive. */
+  endIndex: number
+  /** Column of the start of the chunk on its first line, in UTF-16 code units. */
+  startColumn: number
+  /** Column of the end of the chunk on its last line, in UTF-16 code units. */
+  endColumn: number
+  /** Size of the chunk. */
+  size: number
+  /** Text of the chunk. */
+  text: string
+  /** Name of the tokenizer the chunk was sized with, e.g. `cl100k_base`, if the sizer has one. */
+  tokenizer?: string
+}
+
+/**
+ * Kind of an error thrown by the addon, set as the `code` of the error. Errors thrown by a
+ * JavaScript sizer are passed through unchanged.
+ */
+export type ErrorCode =
+  | 'CodeSplitterError'
+  | 'LanguageError'
+  | 'ParseError'
+  | 'EncodingError'
+  | 'SizerError'
+  | 'InvalidArg'
+
+/** Measures the size of a text: a function, or an object with a `size` method. */
+export type Sizer = ((text: string) => number) | { size(text: string): number }
+
+/** Get the identifiers of the languages with a grammar bundled in the addon. */
+export function languages(): Language[]
+
+/** Get the language of files with the extension, e.g. `"py"` or `".py"`. */
+export function languageFromExtension(extension: string): Language
+
+/** Splitter that measures chunks with a JavaScript sizer. */
+export class Splitter {
+  constructor(language: Language, maxSize: number, sizer: Sizer)
+  /**
+   * Split the code into chunks on a worker thread. The sizer still runs on the JavaScript
+   * thread, which the worker waits for.
+   */
+  split(code: Code): Promise<Chunk[]>
+  /** Split the code into chunks on the JavaScript thread. */
+  splitSync(code: Code): Chunk[]
+}
+
+/** Splitter that measures chunks in characters. */
+export class CharSplitter {
+  constructor(language: Language, maxSize: number)
+  /** Split the code into chunks on a worker thread. */
+  split(code: Code): Promise<Chunk[]>
+  /** Split the code into chunks on the JavaScript thread. */
+  splitSync(code: Code): Chunk[]
+}
+
+/** Splitter that measures chunks in words. */
+export class WordSplitter {
+  constructor(language: Language, maxSize: number)
+  /** Split the code into chunks on a worker thread. */
+  split(code: Code): Promise<Chunk[]>
+  /** Split the code into chunks on the JavaScript thread. */
+  splitSync(code: Code): Chunk[]
+}
+
+/** Options of a `TiktokenSplitter`, which accepts either an encoding or a model. */
+export interface TiktokenOptions {
+  /** Name of the tiktoken encoding, e.g. `o200k_base`. */
+  encoding?: string
+  /** Name of the OpenAI model whose encoding to use, e.g. `gpt-4o`. */
+  model?: string
+}
+
+/** Splitter that measures chunks in tokens of a tiktoken encoding. */
+export class TiktokenSplitter {
+  /**
+   * Split by tokens of a tiktoken encoding, chosen by `encoding` name (e.g. `o200k_base`) or
+   * by OpenAI `model` name (e.g. `gpt-4o`). Defaults to `cl100k_base`.
+   */
+  constructor(language: Language, maxSize: number, options?: TiktokenOptions)
+  /** Name of the encoding, which the model name given to the constructor resolves to. */
+  readonly encoding: string
+  /** Split the code into chunks on a worker thread. */
+  split(code: Code): Promise<Chunk[]>
+  /** Split the code into chunks on the JavaScript thread. */
+  splitSync(code: Code): Chunk[]
+}
+
+/** Splitter that measures chunks in tokens of a HuggingFace tokenizer. */
+export class HuggingfaceSplitter {
+  /**
+   * Split by tokens of a pretrained tokenizer downloaded from the HuggingFace Hub. If
+   * `addSpecialTokens` is set, the special tokens the tokenizer adds to every input, such as
+   * `[CLS]` and `[SEP]`, count toward `maxSize`.
+   */
+  constructor(
+    language: Language,
+    maxSize: number,
+    pretrainedModelNameOrPath: string,
+    addSpecialTokens?: boolean,
+  )
+  /** Split by tokens of a tokenizer loaded from a local `tokenizer.json` file. */
+  static fromFile(
+    language: Language,
+    maxSize: number,
+    path: string,
+    addSpecialTokens?: boolean,
+  ): HuggingfaceSplitter
+  /** Split by tokens of a tokenizer loaded from the contents of a `tokenizer.json` file. */
+  static fromBuffer(
+    language: Language,
+    maxSize: number,
+    data: Buffer,
+    addSpecialTokens?: boolean,
+  ): HuggingfaceSplitter
+  /** Split the code into chunks on a worker thread. */
+  split(code: Code): Promise<Chunk[]>
+  /** Split the code into chunks on the JavaScript thread. */
+  splitSync(code: Code): Chunk[]
+}
@@ -0,0 +1,21 @@
+const { existsSync } = require('fs')
+const { join } = require('path')
+
+// Suffix of the target triple, which tells glibc and musl builds of Linux apart
+function abi() {
+  switch (process.platform) {
+    case 'linux':
+      return process.report.getReport().header.glibcVersionRuntime ? '-gnu' : '-musl'
+    case 'win32':
+      return '-msvc'
+    default:
+      return ''
+  }
+}
+
+// `napi build --platform` names the addon after the target, e.g. code-splitter.linux-x64-gnu.node,
+// and published packages ship it in a package per target, e.g. code-splitter-linux-x64-gnu
+const target = `${process.platform}-${process.arch}${abi()}`
+const local = join(__dirname, `code-splitter.${target}.node`)
+
+module.exports = require(existsSync(local) ? local : `code-splitter-${target}`)
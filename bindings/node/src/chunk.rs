@@ -0,0 +1,126 @@
+use napi::bindgen_prelude::{Buffer, Either};
+use napi_derive::napi;
+
+use ::code_splitter::Encoding;
+
+/// A chunk of code. Offsets are given in bytes of the original code, and in UTF-16 code units
+/// of the code decoded with its encoding, so that `code.slice(startIndex, endIndex) === text`
+/// for a string input.
+///
+/// Tree-sitter keeps offsets and rows in 32 bits, so they always fit a `u32`.
+#[napi(object)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chunk {
+    /// Subtree representation of the chunk.
+    pub subtree: String,
+    /// Row of the first line of the chunk, starting at 0.
+    pub start: u32,
+    /// Row of the last line of the chunk.
+    pub end: u32,
+    /// Byte offset of the start of the chunk.
+    pub start_byte: u32,
+    /// Byte offset of the end of the chunk, exclusive.
+    pub end_byte: u32,
+    /// String index of the start of the chunk.
+    pub start_index: u32,
+    /// String index of the end of the chunk, exclusive.
+    pub end_index: u32,
+    /// Column of the start of the chunk on its first line, in UTF-16 code units.
+    pub start_column: u32,
+    /// Column of the end of the chunk on its last line, in UTF-16 code units.
+    pub end_column: u32,
+    /// Size of the chunk.
+    pub size: u32,
+    /// Text of the chunk.
+    pub text: String,
+    /// Name of the tokenizer the chunk was sized with, e.g. `cl100k_base`, if the sizer has one.
+    pub tokenizer: Option<String>,
+}
+
+impl Chunk {
+    /// Convert the chunks split from the code, which are in order and don't overlap.
+    pub fn from_chunks(chunks: Vec<code_splitter::Chunk>, code: &[u8]) -> Vec<Self> {
+        let Some(first) = chunks.first() else {
+            return Vec::new();
+        };
+        let mut cursor = IndexCursor::new(first.encoding);
+
+        chunks
+            .into_iter()
+            .map(|chunk| Chunk::convert(chunk, code, &mut cursor))
+            .collect()
+    }
+
+    /// Convert a chunk split from the code, moving the cursor to its end.
+    fn convert(chunk: code_splitter::Chunk, code: &[u8], cursor: &mut IndexCursor) -> Self {
+        let (start_index, start_column) = cursor.advance(code, chunk.range.start_byte);
+        let (end_index, end_column) = cursor.advance(code, chunk.range.end_byte);
+        Chunk {
+            text: chunk.text(code),
+            subtree: chunk.subtree,
+            start: to_u32(chunk.range.start_point.row),
+            end: to_u32(chunk.range.end_point.row),
+            start_byte: to_u32(chunk.range.start_byte),
+            end_byte: to_u32(chunk.range.end_byte),
+            start_index,
+            end_index,
+            start_column,
+            end_column,
+            size: to_u32(chunk.size),
+            tokenizer: chunk.tokenizer,
+        }
+    }
+}
+
+/// Convert an offset or a size to a JavaScript number, saturating sizes that a custom sizer
+/// made larger than any offset.
+fn to_u32(n: usize) -> u32 {
+    u32::try_from(n).unwrap_or(u32::MAX)
+}
+
+/// Converts increasing byte offsets in the code into string indices and columns, decoding only
+/// the bytes between consecutive offsets.
+struct IndexCursor {
+    encoding: &'static Encoding,
+    byte: usize,
+    index: u32,
+    column: u32,
+}
+
+impl IndexCursor {
+    fn new(encoding: &'static Encoding) -> Self {
+        Self {
+            encoding,
+            byte: 0,
+            index: 0,
+            column: 0,
+        }
+    }
+
+    /// Move to the byte offset in the code, and return its string index and column.
+    fn advance(&mut self, code: &[u8], byte: usize) -> (u32, u32) {
+        let (text, _) = self
+            .encoding
+            .decode_without_bom_handling(&code[self.byte..byte]);
+        let units = utf16_len(&text);
+        self.column = match text.rfind('\n') {
+            Some(i) => utf16_len(&text[i + 1..]),
+            None => self.column + units,
+        };
+        self.index += units;
+        self.byte = byte;
+        (self.index, self.column)
+    }
+}
+
+fn utf16_len(text: &str) -> u32 {
+    to_u32(text.encode_utf16().count())
+}
+
+/// Get the bytes of code given as a string, or the bytes of code given as a `Buffer`.
+pub fn code_bytes(code: Either<String, Buffer>) -> Vec<u8> {
+    match code {
+        Either::A(text) => text.into_bytes(),
+        Either::B(buffer) => buffer.into(),
+    }
+}
@@ -0,0 +1,36 @@
+use napi::Env;
+
+/// Convert a code-splitter error to a JavaScript `Error` whose `code` names the kind of error,
+/// matching the exception classes of the Python binding. Invalid arguments get the `InvalidArg`
+/// code of napi's own argument errors. Errors thrown by a JavaScript sizer are passed through
+/// unchanged.
+pub fn to_js_error(env: &Env, e: code_splitter::Error) -> napi::Error {
+    use code_splitter::Error;
+
+    let e = match e {
+        Error::Sizer(source) => match source.downcast::<napi::Error>() {
+            Ok(err) => return *err,
+            Err(source) => Error::Sizer(source),
+        },
+        e => e,
+    };
+
+    let code = match e {
+        Error::Language(_) | Error::UnsupportedLanguage(_) | Error::LoadGrammar { .. } => {
+            "LanguageError"
+        }
+        Error::Parse => "ParseError",
+        Error::Utf8(_) => "EncodingError",
+        Error::Sizer(_) => "SizerError",
+        Error::InvalidArgument(_) => "InvalidArg",
+        _ => "CodeSplitterError",
+    };
+    let message = e.to_string();
+    create_error(env, code, &message).unwrap_or_else(|_| napi::Error::from_reason(message))
+}
+
+fn create_error(env: &Env, code: &str, message: &str) -> napi::Result<napi::Error> {
+    let mut error = env.create_error(napi::Error::from_reason(message))?;
+    error.set_named_property("code", env.create_string(code)?)?;
+    Ok(napi::Error::from(error.into_unknown()))
+}
@@ -0,0 +1,35 @@
+use ::code_splitter::{Language, LanguageRegistry};
+use napi::{Env, Result};
+use napi_derive::napi;
+use std::path::Path;
+
+use crate::error::to_js_error;
+
+/// Get the identifiers of the languages with a grammar bundled in the addon, e.g. `"rust"`.
+#[napi]
+pub fn languages() -> Vec<&'static str> {
+    Language::ALL.iter().map(Language::name).collect()
+}
+
+/// Get the language of files with the extension, e.g. `"py"` or `".py"`.
+#[napi]
+pub fn language_from_extension(env: Env, extension: String) -> Result<&'static str> {
+    let extension = extension.strip_prefix('.').unwrap_or(&extension);
+    let path = Path::new("file").with_extension(extension);
+    LanguageRegistry::default()
+        .detect_path(&path)
+        .and_then(|name| name.parse::<Language>().ok())
+        .map(|language| language.name())
+        .ok_or_else(|| {
+            let e = code_splitter::Error::UnsupportedLanguage(format!(".{extension}"));
+            to_js_error(&env, e)
+        })
+}
+
+/// Get the bundled tree-sitter grammar of a language given by its identifier.
+pub fn grammar(env: &Env, language: &str) -> Result<tree_sitter::Language> {
+    language
+        .parse::<Language>()
+        .map(|language| language.tree_sitter_language())
+        .map_err(|e| to_js_error(env, e))
+}
@@ -0,0 +1,14 @@
+mod chunk;
+pub use chunk::Chunk;
+
+mod error;
+
+mod language;
+pub use language::{language_from_extension, languages};
+
+mod sizer;
+
+mod splitter;
+pub use splitter::{
+    CharSplitter, HuggingfaceSplitter, JsSplitter, TiktokenOptions, TiktokenSplitter, WordSplitter,
+};
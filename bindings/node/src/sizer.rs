@@ -0,0 +1,141 @@
+use napi::bindgen_prelude::{Either, FromNapiValue, Function, FunctionRef};
+use napi::threadsafe_function::{ErrorStrategy, ThreadsafeFunction, ThreadsafeFunctionCallMode};
+use napi::{Env, JsFunction, JsUnknown, NapiRaw, Result};
+use std::sync::mpsc;
+
+use ::code_splitter::{Error, Sizer};
+
+/// Wraps a JavaScript sizer, a function `(text) => number` or an object with a `size(text)`
+/// method, into a function that sizes a batch of texts. The wrapper returns the sizes, or the
+/// error the sizer threw instead of throwing it: an exception escaping a threadsafe function
+/// call would abort the process.
+const BATCH_SIZER: &str = r#"(sizer) => {
+  const size =
+    typeof sizer === "function"
+      ? sizer
+      : typeof sizer?.size === "function"
+        ? (text) => sizer.size(text)
+        : undefined;
+  if (size === undefined) {
+    throw new TypeError("sizer must be a function or have a size(text) method");
+  }
+  return (texts) => {
+    try {
+      return texts.map((text) => {
+        const n = size(text);
+        if (!Number.isSafeInteger(n) || n < 0) {
+          throw new TypeError(`sizer must return a non-negative integer, got ${n}`);
+        }
+        return n;
+      });
+    } catch (error) {
+      return error instanceof Object ? error : new Error(String(error));
+    }
+  };
+}"#;
+
+/// The sizes of a batch of texts, or the error the JavaScript sizer threw.
+type BatchResult = Either<Vec<f64>, JsUnknown>;
+
+/// A JavaScript sizer, callable both from the JavaScript thread and from worker threads.
+pub struct JsSizer {
+    /// The batch sizer, for calls on the JavaScript thread.
+    batch: FunctionRef<Vec<String>, BatchResult>,
+    /// The batch sizer, for calls from worker threads.
+    threadsafe: ThreadsafeFunction<Vec<String>, ErrorStrategy::Fatal>,
+}
+
+impl JsSizer {
+    pub fn new(env: &Env, sizer: JsUnknown) -> Result<Self> {
+        let wrap: JsFunction = env.run_script(BATCH_SIZER)?;
+        let batch = wrap.call(None, &[sizer])?;
+        // SAFETY: the wrapper always returns a function
+        let batch = unsafe { batch.cast::<JsFunction>() };
+
+        let mut threadsafe = batch.create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))?;
+        // Don't keep the process alive just because a splitter exists
+        threadsafe.unref(env)?;
+
+        // SAFETY: the value is a function of the same environment
+        let batch: Function<Vec<String>, BatchResult> =
+            unsafe { Function::from_napi_value(env.raw(), batch.raw())? };
+        Ok(JsSizer {
+            batch: batch.create_ref()?,
+            threadsafe,
+        })
+    }
+
+    /// Get a sizer for splitting on the JavaScript thread, which calls the sizer directly.
+    pub fn local<'env>(&self, env: &'env Env) -> Result<LocalSizer<'env>> {
+        Ok(LocalSizer {
+            batch: self.batch.borrow_back(env)?,
+        })
+    }
+
+    /// Get a sizer for splitting on a worker thread, which queues calls to the sizer on the
+    /// JavaScript thread and waits for their results.
+    pub fn threadsafe(&self) -> ThreadsafeSizer {
+        ThreadsafeSizer {
+            batch: self.threadsafe.clone(),
+        }
+    }
+}
+
+pub struct LocalSizer<'env> {
+    batch: Function<'env, Vec<String>, BatchResult>,
+}
+
+impl Sizer for LocalSizer<'_> {
+    fn size(&self, text: &str) -> code_splitter::Result<usize> {
+        Ok(self.size_batch(&[text])?[0])
+    }
+
+    /// Size all texts with a single call into JavaScript.
+    fn size_batch(&self, texts: &[&str]) -> code_splitter::Result<Vec<usize>> {
+        let texts = texts.iter().map(|text| text.to_string()).collect();
+        let sizes = self.batch.call(texts).and_then(into_sizes);
+        sizes.map_err(|e| Error::Sizer(e.into()))
+    }
+}
+
+pub struct ThreadsafeSizer {
+    batch: ThreadsafeFunction<Vec<String>, ErrorStrategy::Fatal>,
+}
+
+impl Sizer for ThreadsafeSizer {
+    fn size(&self, text: &str) -> code_splitter::Result<usize> {
+        Ok(self.size_batch(&[text])?[0])
+    }
+
+    /// Size all texts with a single call into JavaScript, blocking until the JavaScript thread
+    /// has run it.
+    fn size_batch(&self, texts: &[&str]) -> code_splitter::Result<Vec<usize>> {
+        let texts = texts.iter().map(|text| text.to_string()).collect();
+        let (sender, receiver) = mpsc::sync_channel(1);
+        self.batch.call_with_return_value(
+            texts,
+            ThreadsafeFunctionCallMode::Blocking,
+            move |result: BatchResult| {
+                // The receiver only goes away if the worker thread panicked
+                let _ = sender.send(into_sizes(result));
+                Ok(())
+            },
+        );
+        // The sender is dropped without a result if the call was never made, e.g. when the
+        // environment is shutting down
+        let sizes = receiver
+            .recv()
+            .unwrap_or_else(|_| Err(napi::Error::from_reason("sizer is no longer available")));
+        sizes.map_err(|e| Error::Sizer(e.into()))
+    }
+}
+
+/// Convert the result of the batch sizer, keeping the error the JavaScript sizer threw, so that
+/// it reaches the JavaScript caller unchanged.
+fn into_sizes(result: BatchResult) -> Result<Vec<usize>> {
+    match result {
+        // The batch sizer checks that the sizes are non-negative safe integers
+        Either::A(sizes) => Ok(sizes.into_iter().map(|size| size as usize).collect()),
+        Either::B(error) => Err(napi::Error::from(error)),
+    }
+}
@@ -0,0 +1,304 @@
+use napi::bindgen_prelude::{AsyncTask, Buffer, Either};
+use napi::{Env, JsUnknown, Result, Status, Task};
+use napi_derive::napi;
+use std::sync::Arc;
+
+use crate::chunk::{code_bytes, Chunk};
+use crate::error::to_js_error;
+use crate::language::grammar;
+use crate::sizer::JsSizer;
+use ::code_splitter::{
+    CharCounter, HuggingfaceCounter, Sizer, Splitter, TiktokenCounter, WordCounter,
+};
+
+type BoxSplitter = Splitter<Box<dyn Sizer + Send + Sync>>;
+
+struct GenericSplitter {
+    splitter: Arc<BoxSplitter>,
+}
+
+impl GenericSplitter {
+    fn new<T>(env: &Env, language: &str, max_size: u32, sizer: T) -> Result<Self>
+    where
+        T: Sizer + Send + Sync + 'static,
+    {
+        let sizer: Box<dyn Sizer + Send + Sync> = Box::new(sizer);
+        let splitter = new_splitter(env, language, max_size, sizer)?;
+        Ok(GenericSplitter {
+            splitter: Arc::new(splitter),
+        })
+    }
+
+    fn split(&self, code: Either<String, Buffer>) -> AsyncTask<SplitTask> {
+        SplitTask::new(Arc::clone(&self.splitter), code)
+    }
+
+    fn split_sync(&self, env: Env, code: Either<String, Buffer>) -> Result<Vec<Chunk>> {
+        split_sync(env, &*self.splitter, code)
+    }
+}
+
+fn new_splitter<T: Sizer>(
+    env: &Env,
+    language: &str,
+    max_size: u32,
+    sizer: T,
+) -> Result<Splitter<T>> {
+    let splitter =
+        Splitter::new(grammar(env, language)?, sizer).map_err(|e| to_js_error(env, e))?;
+    Ok(splitter.with_max_size(max_size as usize))
+}
+
+fn split_sync<T: Sizer>(
+    env: Env,
+    splitter: &Splitter<T>,
+    code: Either<String, Buffer>,
+) -> Result<Vec<Chunk>> {
+    let code = code_bytes(code);
+    let chunks = splitter.split(&code).map_err(|e| to_js_error(&env, e))?;
+    Ok(Chunk::from_chunks(chunks, &code))
+}
+
+/// Splits code on the libuv thread pool, resolving to its chunks.
+pub struct SplitTask {
+    splitter: Arc<BoxSplitter>,
+    code: Vec<u8>,
+}
+
+impl SplitTask {
+    fn new(splitter: Arc<BoxSplitter>, code: Either<String, Buffer>) -> AsyncTask<Self> {
+        AsyncTask::new(SplitTask {
+            splitter,
+            code: code_bytes(code),
+        })
+    }
+}
+
+impl Task for SplitTask {
+    type Output = code_splitter::Result<Vec<code_splitter::Chunk>>;
+    type JsValue = Vec<Chunk>;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        Ok(self.splitter.split(&self.code))
+    }
+
+    fn resolve(&mut self, env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        let chunks = output.map_err(|e| to_js_error(&env, e))?;
+        Ok(Chunk::from_chunks(chunks, &self.code))
+    }
+}
+
+/// Implement the JavaScript methods of a splitter class, which has its `GenericSplitter` in a
+/// `splitter` field, adding the splitting methods shared by the splitters to the methods given.
+macro_rules! splitter_methods {
+    (impl $name:ident { $($methods:tt)* }) => {
+        #[napi]
+        impl $name {
+            $($methods)*
+
+            /// Split the code, given as a string or a `Buffer`, into chunks on a worker thread.
+            #[napi(ts_return_type = "Promise<Array<Chunk>>")]
+            pub fn split(&self, code: Either<String, Buffer>) -> AsyncTask<SplitTask> {
+                self.splitter.split(code)
+            }
+
+            /// Split the code, given as a string or a `Buffer`, into chunks on the JavaScript
+            /// thread.
+            #[napi]
+            pub fn split_sync(
+                &self,
+                env: Env,
+                code: Either<String, Buffer>,
+            ) -> Result<Vec<Chunk>> {
+                self.splitter.split_sync(env, code)
+            }
+        }
+    };
+}
+
+/// Splitter that measures chunks with a JavaScript sizer: a function `(text) => number`, or an
+/// object with a `size(text)` method.
+#[napi(js_name = "Splitter")]
+pub struct JsSplitter {
+    language: String,
+    max_size: u32,
+    sizer: JsSizer,
+}
+
+#[napi]
+impl JsSplitter {
+    #[napi(constructor)]
+    pub fn new(env: Env, language: String, max_size: u32, sizer: JsUnknown) -> Result<Self> {
+        let sizer = JsSizer::new(&env, sizer)?;
+        // Check the language up front, like the other splitters
+        grammar(&env, &language)?;
+        Ok(JsSplitter {
+            language,
+            max_size,
+            sizer,
+        })
+    }
+
+    /// Split the code, given as a string or a `Buffer`, into chunks on a worker thread. The
+    /// sizer still runs on the JavaScript thread, which the worker waits for.
+    #[napi(ts_return_type = "Promise<Array<Chunk>>")]
+    pub fn split(&self, env: Env, code: Either<String, Buffer>) -> Result<AsyncTask<SplitTask>> {
+        let sizer: Box<dyn Sizer + Send + Sync> = Box::new(self.sizer.threadsafe());
+        let splitter = new_splitter(&env, &self.language, self.max_size, sizer)?;
+        Ok(SplitTask::new(Arc::new(splitter), code))
+    }
+
+    /// Split the code, given as a string or a `Buffer`, into chunks on the JavaScript thread.
+    #[napi]
+    pub fn split_sync(&self, env: Env, code: Either<String, Buffer>) -> Result<Vec<Chunk>> {
+        let splitter = new_splitter(&env, &self.language, self.max_size, self.sizer.local(&env)?)?;
+        split_sync(env, &splitter, code)
+    }
+}
+
+#[napi]
+pub struct CharSplitter {
+    splitter: GenericSplitter,
+}
+
+splitter_methods! {
+    impl CharSplitter {
+        #[napi(constructor)]
+        pub fn new(env: Env, language: String, max_size: u32) -> Result<Self> {
+            let splitter = GenericSplitter::new(&env, &language, max_size, CharCounter)?;
+            Ok(CharSplitter { splitter })
+        }
+    }
+}
+
+#[napi]
+pub struct WordSplitter {
+    splitter: GenericSplitter,
+}
+
+splitter_methods! {
+    impl WordSplitter {
+        #[napi(constructor)]
+        pub fn new(env: Env, language: String, max_size: u32) -> Result<Self> {
+            let splitter = GenericSplitter::new(&env, &language, max_size, WordCounter)?;
+            Ok(WordSplitter { splitter })
+        }
+    }
+}
+
+/// Options of a `TiktokenSplitter`.
+#[napi(object)]
+pub struct TiktokenOptions {
+    /// Name of the tiktoken encoding, e.g. `o200k_base`.
+    pub encoding: Option<String>,
+    /// Name of the OpenAI model whose encoding to use, e.g. `gpt-4o`.
+    pub model: Option<String>,
+}
+
+#[napi]
+pub struct TiktokenSplitter {
+    splitter: GenericSplitter,
+    encoding: &'static str,
+}
+
+splitter_methods! {
+    impl TiktokenSplitter {
+        /// Split by tokens of a tiktoken encoding, chosen by `encoding` name (e.g. `o200k_base`) or
+        /// by OpenAI `model` name (e.g. `gpt-4o`). Defaults to `cl100k_base`.
+        #[napi(constructor)]
+        pub fn new(
+            env: Env,
+            language: String,
+            max_size: u32,
+            options: Option<TiktokenOptions>,
+        ) -> Result<Self> {
+            let (encoding, model) = options.map_or((None, None), |o| (o.encoding, o.model));
+            let counter = match (encoding, model) {
+                (Some(_), Some(_)) => {
+                    return Err(napi::Error::new(
+                        Status::InvalidArg,
+                        "encoding and model cannot both be given",
+                    ))
+                }
+                (_, Some(model)) => TiktokenCounter::for_model(&model),
+                (encoding, None) => TiktokenCounter::new(encoding.as_deref().unwrap_or("cl100k_base")),
+            }
+            .map_err(|e| to_js_error(&env, e))?;
+            let encoding = counter.encoding();
+            let splitter = GenericSplitter::new(&env, &language, max_size, counter)?;
+            Ok(TiktokenSplitter { splitter, encoding })
+        }
+
+        /// Name of the encoding, which the model name given to the constructor resolves to.
+        #[napi(getter)]
+        pub fn encoding(&self) -> &'static str {
+            self.encoding
+        }
+    }
+}
+
+#[napi]
+pub struct HuggingfaceSplitter {
+    splitter: GenericSplitter,
+}
+
+impl HuggingfaceSplitter {
+    fn with_counter(
+        env: &Env,
+        language: &str,
+        max_size: u32,
+        counter: code_splitter::Result<HuggingfaceCounter>,
+        add_special_tokens: Option<bool>,
+    ) -> Result<Self> {
+        let counter = counter
+            .map_err(|e| to_js_error(env, e))?
+            .with_special_tokens(add_special_tokens.unwrap_or(false));
+        let splitter = GenericSplitter::new(env, language, max_size, counter)?;
+        Ok(HuggingfaceSplitter { splitter })
+    }
+}
+
+splitter_methods! {
+    impl HuggingfaceSplitter {
+        /// Split by tokens of a pretrained tokenizer downloaded from the HuggingFace Hub. If
+        /// `addSpecialTokens` is set, the special tokens the tokenizer adds to every input, such as
+        /// `[CLS]` and `[SEP]`, count toward `maxSize`.
+        #[napi(constructor)]
+        pub fn new(
+            env: Env,
+            language: String,
+            max_size: u32,
+            pretrained_model_name_or_path: String,
+            add_special_tokens: Option<bool>,
+        ) -> Result<Self> {
+            let counter = HuggingfaceCounter::from_pretrained(&pretrained_model_name_or_path);
+            Self::with_counter(&env, &language, max_size, counter, add_special_tokens)
+        }
+
+        /// Split by tokens of a tokenizer loaded from a local `tokenizer.json` file.
+        #[napi(factory)]
+        pub fn from_file(
+            env: Env,
+            language: String,
+            max_size: u32,
+            path: String,
+            add_special_tokens: Option<bool>,
+        ) -> Result<Self> {
+            let counter = HuggingfaceCounter::from_file(path);
+            Self::with_counter(&env, &language, max_size, counter, add_special_tokens)
+        }
+
+        /// Split by tokens of a tokenizer loaded from the contents of a `tokenizer.json` file.
+        #[napi(factory)]
+        pub fn from_buffer(
+            env: Env,
+            language: String,
+            max_size: u32,
+            data: Buffer,
+            add_special_tokens: Option<bool>,
+        ) -> Result<Self> {
+            let counter = HuggingfaceCounter::from_bytes(&data);
+            Self::with_counter(&env, &language, max_size, counter, add_special_tokens)
+        }
+    }
+}
@@ -0,0 +1,435 @@
+from collections.abc import Callable, Iterator, Sequence
+from os import PathLike
+from typing import Any, ClassVar, Literal, Protocol, TypeAlias, TypeVar, final, overload
+
+class _SizerObject(Protocol):
+    def size(self, text: str, /) -> int: ...
+
+# A sizer is a callable `(text) -> int`, or an object with a `size(text) -> int` method.
+_Sizer: TypeAlias = Callable[[str], int] | _SizerObject
+
+# Besides the bundled `Language`, splitters accept the capsule returned by `language()` in the
+# `tree_sitter_<lang>` packages. `tree_sitter.Language` objects are not supported and raise
+# `TypeError`, since py-tree-sitter has no public API that gives out the grammar they wrap: pass
+# the capsule they were created from instead. Splitters built from a capsule cannot be pickled.
+_LanguageLike: TypeAlias = Language | Any
+
+_T = TypeVar("_T", Chunk, ChunkOffsets)
+
+_Code: TypeAlias = str | bytes
+_Path: TypeAlias = str | PathLike[str]
+
+class CodeSplitterError(RuntimeError):
+    """Base class of all errors raised by code_splitter."""
+
+class LanguageError(CodeSplitterError):
+    """The tree-sitter grammar could not be loaded."""
+
+class ParseError(CodeSplitterError):
+    """Tree-sitter failed to parse the code."""
+
+class EncodingError(CodeSplitterError):
+    """The code could not be decoded."""
+
+class SizerError(CodeSplitterError):
+    """The sizer failed to measure the size of a text."""
+
+@final
+class Language:
+    """A language with a grammar bundled in the wheel."""
+
+    Bash: ClassVar[Language]
+    C: ClassVar[Language]
+    Cpp: ClassVar[Language]
+    CSharp: ClassVar[Language]
+    Css: ClassVar[Language]
+    Golang: ClassVar[Language]
+    Html: ClassVar[Language]
+    Java: ClassVar[Language]
+    JavaScript: ClassVar[Language]
+    Json: ClassVar[Language]
+    Kotlin: ClassVar[Language]
+    Markdown: ClassVar[Language]
+    Php: ClassVar[Language]
+    Python: ClassVar[Language]
+    Ruby: ClassVar[Language]
+    Rust: ClassVar[Language]
+    Sql: ClassVar[Language]
+    Swift: ClassVar[Language]
+    Toml: ClassVar[Language]
+    TypeScript: ClassVar[Language]
+    Tsx: ClassVar[Language]
+    Yaml: ClassVar[Language]
+
+    @staticmethod
+    def from_extension(extension: str) -> Language:
+        """Get the language of files with the extension, e.g. `"py"` or `".py"`. Raises
+        `ValueError` for an unknown extension."""
+
+@final
+class Chunk:
+    """A chunk of code. Offsets are given in bytes of the original code, and in characters of
+    the code decoded with its encoding, so that `code[start_char:end_char] == text` for a `str`
+    input."""
+
+    @property
+    def subtree(self) -> str:
+        """Subtree representation of the chunk."""
+    @property
+    def start(self) -> int:
+        """Row of the first line of the chunk, starting at 0."""
+    @property
+    def end(self) -> int:
+        """Row of the last line of the chunk."""
+    @property
+    def start_byte(self) -> int:
+        """Byte offset of the start of the chunk."""
+    @property
+    def end_byte(self) -> int:
+        """Byte offset of the end of the chunk, exclusive."""
+    @property
+    def start_char(self) -> int:
+        """Character offset of the start of the chunk."""
+    @property
+    def end_char(self) -> int:
+        """Character offset of the end of the chunk, exclusive."""
+    @property
+    def start_column(self) -> int:
+        """Column of the start of the chunk on its first line, in characters."""
+    @property
+    def end_column(self) -> int:
+        """Column of the end of the chunk on its last line, in characters."""
+    @property
+    def size(self) -> int:
+        """Size of the chunk."""
+    @property
+    def text(self) -> str:
+        """Text of the chunk."""
+    @property
+    def tokenizer(self) -> str | None:
+        """Name of the tokenizer the chunk was sized with, e.g. `cl100k_base`, or `None`."""
+    def __init__(
+        self,
+        subtree: str,
+        start: int,
+        end: int,
+        start_byte: int,
+        end_byte: int,
+        start_char: int,
+        end_char: int,
+        start_column: int,
+        end_column: int,
+        size: int,
+        text: str,
+        tokenizer: str | None = None,
+    ) -> None: ...
+    def to_dict(self) -> dict[str, Any]:
+        """Get the fields of the chunk as a dict."""
+    def __eq__(self, other: object) -> bool: ...
+
+@final
+class ChunkOffsets:
+    """The offsets of a chunk of code, without its text, for callers that slice the code
+    themselves: `code[start_byte:end_byte]` for a `bytes` input, or
+    `code[start_char:end_char]` for a `str` input."""
+
+    @property
+    def start(self) -> int:
+        """Row of the first line of the chunk, starting at 0."""
+    @property
+    def end(self) -> int:
+        """Row of the last line of the chunk."""
+    @property
+    def start_byte(self) -> int:
+        """Byte offset of the start of the chunk."""
+    @property
+    def end_byte(self) -> int:
+        """Byte offset of the end of the chunk, exclusive."""
+    @property
+    def start_char(self) -> int:
+        """Character offset of the start of the chunk."""
+    @property
+    def end_char(self) -> int:
+        """Character offset of the end of the chunk, exclusive."""
+    @property
+    def start_column(self) -> int:
+        """Column of the start of the chunk on its first line, in characters."""
+    @property
+    def end_column(self) -> int:
+        """Column of the end of the chunk on its last line, in characters."""
+    @property
+    def size(self) -> int:
+        """Size of the chunk."""
+    @property
+    def tokenizer(self) -> str | None:
+        """Name of the tokenizer the chunk was sized with, e.g. `cl100k_base`, or `None`."""
+    def __init__(
+        self,
+        start: int,
+        end: int,
+        start_byte: int,
+        end_byte: int,
+        start_char: int,
+        end_char: int,
+        start_column: int,
+        end_column: int,
+        size: int,
+        tokenizer: str | None = None,
+    ) -> None: ...
+    def __eq__(self, other: object) -> bool: ...
+
+@final
+class ChunkIterator(Iterator[_T]):
+    """Iterator over the chunks of a code. The code is split up front, but the Python chunks
+    and their texts are only created as the iterator advances."""
+
+    def __iter__(self) -> ChunkIterator[_T]: ...
+    def __next__(self) -> _T: ...
+    def __length_hint__(self) -> int:
+        """Number of chunks left."""
+
+@final
+class Splitter:
+    """Splitter that measures chunks with a Python sizer: a callable `(text) -> int`, or an
+    object with a `size(text) -> int` method."""
+
+    def __init__(self, language: _LanguageLike, max_size: int, sizer: _Sizer) -> None: ...
+    def split(self, code: _Code) -> list[Chunk]:
+        """Split the code, given as `bytes` or `str`, into chunks."""
+    @overload
+    def iter_split(
+        self, code: _Code, offsets_only: Literal[False] = False
+    ) -> ChunkIterator[Chunk]:
+        """Split the code into chunks lazily, creating each chunk and copying its text only
+        when the iterator reaches it. With `offsets_only`, `ChunkOffsets` without the text are
+        yielded instead."""
+    @overload
+    def iter_split(
+        self, code: _Code, offsets_only: Literal[True]
+    ) -> ChunkIterator[ChunkOffsets]: ...
+    @overload
+    def iter_split(
+        self, code: _Code, offsets_only: bool
+    ) -> ChunkIterator[Chunk] | ChunkIterator[ChunkOffsets]: ...
+    @overload
+    def split_many(
+        self, codes: Sequence[_Code], return_exceptions: Literal[False] = False
+    ) -> list[list[Chunk]]:
+        """Split several codes in parallel, returning the chunks of each."""
+    @overload
+    def split_many(
+        self, codes: Sequence[_Code], return_exceptions: bool
+    ) -> list[list[Chunk] | Exception]: ...
+    @overload
+    def split_files(
+        self, paths: Sequence[_Path], return_exceptions: Literal[False] = False
+    ) -> list[list[Chunk]]:
+        """Read and split several files in parallel, returning the chunks of each."""
+    @overload
+    def split_files(
+        self, paths: Sequence[_Path], return_exceptions: bool
+    ) -> list[list[Chunk] | Exception]: ...
+    def __reduce__(self) -> tuple[Any, ...]:
+        """Pickle the splitter by its arguments. Raises `TypeError` if `language` was given as a
+        tree-sitter capsule, since capsules cannot be pickled."""
+
+@final
+class CharSplitter:
+    def __init__(self, language: _LanguageLike, max_size: int) -> None: ...
+    def split(self, code: _Code) -> list[Chunk]:
+        """Split the code, given as `bytes` or `str`, into chunks."""
+    @overload
+    def iter_split(
+        self, code: _Code, offsets_only: Literal[False] = False
+    ) -> ChunkIterator[Chunk]:
+        """Split the code into chunks lazily, creating each chunk and copying its text only
+        when the iterator reaches it. With `offsets_only`, `ChunkOffsets` without the text are
+        yielded instead."""
+    @overload
+    def iter_split(
+        self, code: _Code, offsets_only: Literal[True]
+    ) -> ChunkIterator[ChunkOffsets]: ...
+    @overload
+    def iter_split(
+        self, code: _Code, offsets_only: bool
+    ) -> ChunkIterator[Chunk] | ChunkIterator[ChunkOffsets]: ...
+    @overload
+    def split_many(
+        self, codes: Sequence[_Code], return_exceptions: Literal[False] = False
+    ) -> list[list[Chunk]]:
+        """Split several codes in parallel, returning the chunks of each."""
+    @overload
+    def split_many(
+        self, codes: Sequence[_Code], return_exceptions: bool
+    ) -> list[list[Chunk] | Exception]: ...
+    @overload
+    def split_files(
+        self, paths: Sequence[_Path], return_exceptions: Literal[False] = False
+    ) -> list[list[Chunk]]:
+        """Read and split several files in parallel, returning the chunks of each."""
+    @overload
+    def split_files(
+        self, paths: Sequence[_Path], return_exceptions: bool
+    ) -> list[list[Chunk] | Exception]: ...
+    def __reduce__(self) -> tuple[Any, ...]:
+        """Pickle the splitter by its arguments. Raises `TypeError` if `language` was given as a
+        tree-sitter capsule, since capsules cannot be pickled."""
+
+@final
+class WordSplitter:
+    def __init__(self, language: _LanguageLike, max_size: int) -> None: ...
+    def split(self, code: _Code) -> list[Chunk]:
+        """Split the code, given as `bytes` or `str`, into chunks."""
+    @overload
+    def iter_split(
+        self, code: _Code, offsets_only: Literal[False] = False
+    ) -> ChunkIterator[Chunk]:
+        """Split the code into chunks lazily, creating each chunk and copying its text only
+        when the iterator reaches it. With `offsets_only`, `ChunkOffsets` without the text are
+        yielded instead."""
+    @overload
+    def iter_split(
+        self, code: _Code, offsets_only: Literal[True]
+    ) -> ChunkIterator[ChunkOffsets]: ...
+    @overload
+    def iter_split(
+        self, code: _Code, offsets_only: bool
+    ) -> ChunkIterator[Chunk] | ChunkIterator[ChunkOffsets]: ...
+    @overload
+    def split_many(
+        self, codes: Sequence[_Code], return_exceptions: Literal[False] = False
+    ) -> list[list[Chunk]]:
+        """Split several codes in parallel, returning the chunks of each."""
+    @overload
+    def split_many(
+        self, codes: Sequence[_Code], return_exceptions: bool
+    ) -> list[list[Chunk] | Exception]: ...
+    @overload
+    def split_files(
+        self, paths: Sequence[_Path], return_exceptions: Literal[False] = False
+    ) -> list[list[Chunk]]:
+        """Read and split several files in parallel, returning the chunks of each."""
+    @overload
+    def split_files(
+        self, paths: Sequence[_Path], return_exceptions: bool
+    ) -> list[list[Chunk] | Exception]: ...
+    def __reduce__(self) -> tuple[Any, ...]:
+        """Pickle the splitter by its arguments. Raises `TypeError` if `language` was given as a
+        tree-sitter capsule, since capsules cannot be pickled."""
+
+@final
+class TiktokenSplitter:
+    def __init__(
+        self,
+        language: _LanguageLike,
+        max_size: int,
+        encoding: str | None = None,
+        model: str | None = None,
+    ) -> None:
+        """Split by tokens of a tiktoken encoding, chosen by `encoding` name (e.g. `o200k_base`)
+        or by OpenAI `model` name (e.g. `gpt-4o`). Defaults to `cl100k_base`."""
+    def split(self, code: _Code) -> list[Chunk]:
+        """Split the code, given as `bytes` or `str`, into chunks."""
+    @overload
+    def iter_split(
+        self, code: _Code, offsets_only: Literal[False] = False
+    ) -> ChunkIterator[Chunk]:
+        """Split the code into chunks lazily, creating each chunk and copying its text only
+        when the iterator reaches it. With `offsets_only`, `ChunkOffsets` without the text are
+        yielded instead."""
+    @overload
+    def iter_split(
+        self, code: _Code, offsets_only: Literal[True]
+    ) -> ChunkIterator[ChunkOffsets]: ...
+    @overload
+    def iter_split(
+        self, code: _Code, offsets_only: bool
+    ) -> ChunkIterator[Chunk] | ChunkIterator[ChunkOffsets]: ...
+    @overload
+    def split_many(
+        self, codes: Sequence[_Code], return_exceptions: Literal[False] = False
+    ) -> list[list[Chunk]]:
+        """Split several codes in parallel, returning the chunks of each."""
+    @overload
+    def split_many(
+        self, codes: Sequence[_Code], return_exceptions: bool
+    ) -> list[list[Chunk] | Exception]: ...
+    @overload
+    def split_files(
+        self, paths: Sequence[_Path], return_exceptions: Literal[False] = False
+    ) -> list[list[Chunk]]:
+        """Read and split several files in parallel, returning the chunks of each."""
+    @overload
+    def split_files(
+        self, paths: Sequence[_Path], return_exceptions: bool
+    ) -> list[list[Chunk] | Exception]: ...
+    def __reduce__(self) -> tuple[Any, ...]:
+        """Pickle the splitter by its arguments. Raises `TypeError` if `language` was given as a
+        tree-sitter capsule, since capsules cannot be pickled."""
+
+@final
+class HuggingfaceSplitter:
+    def __init__(
+        self,
+        language: _LanguageLike,
+        max_size: int,
+        pretrained_model_name_or_path: str,
+        add_special_tokens: bool = False,
+    ) -> None:
+        """Split by tokens of a pretrained tokenizer downloaded from the HuggingFace Hub. If
+        `add_special_tokens` is set, the special tokens the tokenizer adds to every input, such
+        as `[CLS]` and `[SEP]`, count toward `max_size`."""
+    @staticmethod
+    def from_file(
+        language: _LanguageLike,
+        max_size: int,
+        path: _Path,
+        add_special_tokens: bool = False,
+    ) -> HuggingfaceSplitter:
+        """Split by tokens of a tokenizer loaded from a local `tokenizer.json` file."""
+    @staticmethod
+    def from_bytes(
+        language: _LanguageLike,
+        max_size: int,
+        data: bytes,
+        add_special_tokens: bool = False,
+    ) -> HuggingfaceSplitter:
+        """Split by tokens of a tokenizer loaded from the bytes of a `tokenizer.json` file."""
+    def split(self, code: _Code) -> list[Chunk]:
+        """Split the code, given as `bytes` or `str`, into chunks."""
+    @overload
+    def iter_split(
+        self, code: _Code, offsets_only: Literal[False] = False
+    ) -> ChunkIterator[Chunk]:
+        """Split the code into chunks lazily, creating each chunk and copying its text only
+        when the iterator reaches it. With `offsets_only`, `ChunkOffsets` without the text are
+        yielded instead."""
+    @overload
+    def iter_split(
+        self, code: _Code, offsets_only: Literal[True]
+    ) -> ChunkIterator[ChunkOffsets]: ...
+    @overload
+    def iter_split(
+        self, code: _Code, offsets_only: bool
+    ) -> ChunkIterator[Chunk] | ChunkIterator[ChunkOffsets]: ...
+    @overload
+    def split_many(
+        self, codes: Sequence[_Code], return_exceptions: Literal[False] = False
+    ) -> list[list[Chunk]]:
+        """Split several codes in parallel, returning the chunks of each."""
+    @overload
+    def split_many(
+        self, codes: Sequence[_Code], return_exceptions: bool
+    ) -> list[list[Chunk] | Exception]: ...
+    @overload
+    def split_files(
+        self, paths: Sequence[_Path], return_exceptions: Literal[False] = False
+    ) -> list[list[Chunk]]:
+        """Read and split several files in parallel, returning the chunks of each."""
+    @overload
+    def split_files(
+        self, paths: Sequence[_Path], return_exceptions: bool
+    ) -> list[list[Chunk] | Exception]: ...
+    def __reduce__(self) -> tuple[Any, ...]:
+        """Pickle the splitter by its arguments. Raises `TypeError` if `language` was given as a
+        tree-sitter capsule, since capsules cannot be pickled."""
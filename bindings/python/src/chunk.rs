@@ -1,15 +1,300 @@
 use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyType};
 
-#[pyclass]
+use ::code_splitter::Encoding;
+
+/// A chunk of code. Offsets are given in bytes of the original code, and in characters of the
+/// code decoded with its encoding, so that `code[start_char:end_char] == text` for a `str` input.
+#[pyclass(module = "code_splitter", eq, get_all)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Chunk {
-    #[pyo3(get)]
+    /// Subtree representation of the chunk.
     pub subtree: String,
-    #[pyo3(get)]
+    /// Row of the first line of the chunk, starting at 0.
     pub start: usize,
-    #[pyo3(get)]
+    /// Row of the last line of the chunk.
     pub end: usize,
-    #[pyo3(get)]
+    /// Byte offset of the start of the chunk.
+    pub start_byte: usize,
+    /// Byte offset of the end of the chunk, exclusive.
+    pub end_byte: usize,
+    /// Character offset of the start of the chunk.
+    pub start_char: usize,
+    /// Character offset of the end of the chunk, exclusive.
+    pub end_char: usize,
+    /// Column of the start of the chunk on its first line, in characters.
+    pub start_column: usize,
+    /// Column of the end of the chunk on its last line, in characters.
+    pub end_column: usize,
+    /// Size of the chunk.
     pub size: usize,
-    #[pyo3(get)]
+    /// Text of the chunk.
     pub text: String,
+    /// Name of the tokenizer the chunk was sized with, e.g. `cl100k_base`, or `None`.
+    pub tokenizer: Option<String>,
+}
+
+impl Chunk {
+    /// Convert the chunks split from the code, which are in order and don't overlap.
+    pub fn from_chunks(chunks: Vec<code_splitter::Chunk>, code: &[u8]) -> Vec<Self> {
+        let Some(first) = chunks.first() else {
+            return Vec::new();
+        };
+        let mut cursor = CharCursor::new(first.encoding);
+
+        chunks
+            .into_iter()
+            .map(|chunk| Chunk::convert(chunk, code, &mut cursor))
+            .collect()
+    }
+
+    /// Convert a chunk split from the code, moving the cursor to its end.
+    pub fn convert(chunk: code_splitter::Chunk, code: &[u8], cursor: &mut CharCursor) -> Self {
+        let (start_char, start_column) = cursor.advance(code, chunk.range.start_byte);
+        let (end_char, end_column) = cursor.advance(code, chunk.range.end_byte);
+        Chunk {
+            text: chunk.text(code),
+            subtree: chunk.subtree,
+            start: chunk.range.start_point.row,
+            end: chunk.range.end_point.row,
+            start_byte: chunk.range.start_byte,
+            end_byte: chunk.range.end_byte,
+            start_char,
+            end_char,
+            start_column,
+            end_column,
+            size: chunk.size,
+            tokenizer: chunk.tokenizer,
+        }
+    }
+}
+
+#[pymethods]
+impl Chunk {
+    #[new]
+    #[pyo3(signature = (subtree, start, end, start_byte, end_byte, start_char, end_char, start_column, end_column, size, text, tokenizer=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        subtree: String,
+        start: usize,
+        end: usize,
+        start_byte: usize,
+        end_byte: usize,
+        start_char: usize,
+        end_char: usize,
+        start_column: usize,
+        end_column: usize,
+        size: usize,
+        text: String,
+        tokenizer: Option<String>,
+    ) -> Self {
+        Chunk {
+            subtree,
+            start,
+            end,
+            start_byte,
+            end_byte,
+            start_char,
+            end_char,
+            start_column,
+            end_column,
+            size,
+            text,
+            tokenizer,
+        }
+    }
+
+    /// Pickle the chunk by its fields, so that chunks can be returned from worker processes.
+    fn __reduce__<'py>(&self, py: Python<'py>) -> (Bound<'py, PyType>, PyObject) {
+        let args = (
+            self.subtree.clone(),
+            self.start,
+            self.end,
+            self.start_byte,
+            self.end_byte,
+            self.start_char,
+            self.end_char,
+            self.start_column,
+            self.end_column,
+            self.size,
+            self.text.clone(),
+            self.tokenizer.clone(),
+        );
+        (py.get_type_bound::<Self>(), args.into_py(py))
+    }
+
+    fn __repr__(&self) -> String {
+        // Keep the representation readable for long chunks
+        let mut text = self.text.chars().take(40).collect::<String>();
+        if text.len() < self.text.len() {
+            text.push_str("...");
+        }
+        format!(
+            "Chunk(start={}, end={}, start_char={}, end_char={}, size={}, text={:?})",
+            self.start, self.end, self.start_char, self.end_char, self.size, text
+        )
+    }
+
+    /// Get the fields of the chunk as a dict.
+    fn to_dict<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let dict = PyDict::new_bound(py);
+        dict.set_item("subtree", &self.subtree)?;
+        dict.set_item("start", self.start)?;
+        dict.set_item("end", self.end)?;
+        dict.set_item("start_byte", self.start_byte)?;
+        dict.set_item("end_byte", self.end_byte)?;
+        dict.set_item("start_char", self.start_char)?;
+        dict.set_item("end_char", self.end_char)?;
+        dict.set_item("start_column", self.start_column)?;
+        dict.set_item("end_column", self.end_column)?;
+        dict.set_item("size", self.size)?;
+        dict.set_item("text", &self.text)?;
+        dict.set_item("tokenizer", &self.tokenizer)?;
+        Ok(dict)
+    }
+}
+
+/// The offsets of a chunk of code, without its text, for callers that slice the code
+/// themselves: `code[start_byte:end_byte]` for a `bytes` input, or `code[start_char:end_char]`
+/// for a `str` input.
+#[pyclass(module = "code_splitter", eq, get_all)]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChunkOffsets {
+    /// Row of the first line of the chunk, starting at 0.
+    pub start: usize,
+    /// Row of the last line of the chunk.
+    pub end: usize,
+    /// Byte offset of the start of the chunk.
+    pub start_byte: usize,
+    /// Byte offset of the end of the chunk, exclusive.
+    pub end_byte: usize,
+    /// Character offset of the start of the chunk.
+    pub start_char: usize,
+    /// Character offset of the end of the chunk, exclusive.
+    pub end_char: usize,
+    /// Column of the start of the chunk on its first line, in characters.
+    pub start_column: usize,
+    /// Column of the end of the chunk on its last line, in characters.
+    pub end_column: usize,
+    /// Size of the chunk.
+    pub size: usize,
+    /// Name of the tokenizer the chunk was sized with, e.g. `cl100k_base`, or `None`.
+    pub tokenizer: Option<String>,
+}
+
+impl ChunkOffsets {
+    /// Convert a chunk split from the code, moving the cursor to its end.
+    pub fn convert(chunk: code_splitter::Chunk, code: &[u8], cursor: &mut CharCursor) -> Self {
+        let (start_char, start_column) = cursor.advance(code, chunk.range.start_byte);
+        let (end_char, end_column) = cursor.advance(code, chunk.range.end_byte);
+        ChunkOffsets {
+            start: chunk.range.start_point.row,
+            end: chunk.range.end_point.row,
+            start_byte: chunk.range.start_byte,
+            end_byte: chunk.range.end_byte,
+            start_char,
+            end_char,
+            start_column,
+            end_column,
+            size: chunk.size,
+            tokenizer: chunk.tokenizer,
+        }
+    }
+}
+
+#[pymethods]
+impl ChunkOffsets {
+    #[new]
+    #[pyo3(signature = (start, end, start_byte, end_byte, start_char, end_char, start_column, end_column, size, tokenizer=None))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        start: usize,
+        end: usize,
+        start_byte: usize,
+        end_byte: usize,
+        start_char: usize,
+        end_char: usize,
+        start_column: usize,
+        end_column: usize,
+        size: usize,
+        tokenizer: Option<String>,
+    ) -> Self {
+        ChunkOffsets {
+            start,
+            end,
+            start_byte,
+            end_byte,
+            start_char,
+            end_char,
+            start_column,
+            end_column,
+            size,
+            tokenizer,
+        }
+    }
+
+    /// Pickle the offsets by their fields.
+    fn __reduce__<'py>(&self, py: Python<'py>) -> (Bound<'py, PyType>, PyObject) {
+        let args = (
+            self.start,
+            self.end,
+            self.start_byte,
+            self.end_byte,
+            self.start_char,
+            self.end_char,
+            self.start_column,
+            self.end_column,
+            self.size,
+            self.tokenizer.clone(),
+        );
+        (py.get_type_bound::<Self>(), args.into_py(py))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ChunkOffsets(start={}, end={}, start_byte={}, end_byte={}, start_char={}, end_char={}, size={})",
+            self.start,
+            self.end,
+            self.start_byte,
+            self.end_byte,
+            self.start_char,
+            self.end_char,
+            self.size
+        )
+    }
+}
+
+/// Converts increasing byte offsets in the code into character offsets and columns, decoding
+/// only the bytes between consecutive offsets.
+pub struct CharCursor {
+    encoding: &'static Encoding,
+    byte: usize,
+    char: usize,
+    column: usize,
+}
+
+impl CharCursor {
+    pub fn new(encoding: &'static Encoding) -> Self {
+        Self {
+            encoding,
+            byte: 0,
+            char: 0,
+            column: 0,
+        }
+    }
+
+    /// Move to the byte offset in the code, and return its character offset and column.
+    pub fn advance(&mut self, code: &[u8], byte: usize) -> (usize, usize) {
+        let (text, _) = self
+            .encoding
+            .decode_without_bom_handling(&code[self.byte..byte]);
+        let chars = text.chars().count();
+        self.column = match text.rfind('\n') {
+            Some(i) => text[i + 1..].chars().count(),
+            None => self.column + chars,
+        };
+        self.char += chars;
+        self.byte = byte;
+        (self.char, self.column)
+    }
 }
@@ -0,0 +1,60 @@
+use pyo3::create_exception;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+
+create_exception!(
+    code_splitter,
+    CodeSplitterError,
+    PyRuntimeError,
+    "Base class of all errors raised by code_splitter."
+);
+create_exception!(
+    code_splitter,
+    LanguageError,
+    CodeSplitterError,
+    "The tree-sitter grammar could not be loaded."
+);
+create_exception!(
+    code_splitter,
+    ParseError,
+    CodeSplitterError,
+    "Tree-sitter failed to parse the code."
+);
+create_exception!(
+    code_splitter,
+    EncodingError,
+    CodeSplitterError,
+    "The code could not be decoded."
+);
+create_exception!(
+    code_splitter,
+    SizerError,
+    CodeSplitterError,
+    "The sizer failed to measure the size of a text."
+);
+
+/// Convert a code-splitter error to the matching Python exception, or to `ValueError` for an
+/// invalid argument. Exceptions raised by a Python sizer are passed through unchanged.
+pub fn to_py_err(e: code_splitter::Error) -> PyErr {
+    use code_splitter::Error;
+
+    let e = match e {
+        Error::Sizer(source) => match source.downcast::<PyErr>() {
+            Ok(err) => return *err,
+            Err(source) => Error::Sizer(source),
+        },
+        e => e,
+    };
+
+    let message = e.to_string();
+    match e {
+        Error::Language(_) | Error::UnsupportedLanguage(_) | Error::LoadGrammar { .. } => {
+            LanguageError::new_err(message)
+        }
+        Error::Parse => ParseError::new_err(message),
+        Error::Utf8(_) => EncodingError::new_err(message),
+        Error::Sizer(_) => SizerError::new_err(message),
+        Error::InvalidArgument(_) => PyValueError::new_err(message),
+        _ => CodeSplitterError::new_err(message),
+    }
+}
@@ -0,0 +1,59 @@
+use pyo3::prelude::*;
+use std::vec;
+
+use crate::chunk::{CharCursor, Chunk, ChunkOffsets};
+use crate::splitter::code_bytes;
+
+/// Iterator over the chunks of a code. The code is split up front, but the Python chunks and
+/// their texts are only created as the iterator advances.
+#[pyclass(module = "code_splitter")]
+pub struct ChunkIterator {
+    /// The code as given from Python, `bytes` or `str`, which the chunks are sliced from.
+    code: PyObject,
+    chunks: vec::IntoIter<code_splitter::Chunk>,
+    /// Cursor at the end of the last chunk, created with the encoding of the first chunk.
+    cursor: Option<CharCursor>,
+    /// Whether to yield `ChunkOffsets` instead of `Chunk`.
+    offsets_only: bool,
+}
+
+impl ChunkIterator {
+    pub fn new(code: PyObject, chunks: Vec<code_splitter::Chunk>, offsets_only: bool) -> Self {
+        Self {
+            code,
+            chunks: chunks.into_iter(),
+            cursor: None,
+            offsets_only,
+        }
+    }
+}
+
+#[pymethods]
+impl ChunkIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        let Some(chunk) = self.chunks.next() else {
+            return Ok(None);
+        };
+        let code = self.code.bind(py);
+        let code = code_bytes(code)?;
+        let cursor = self
+            .cursor
+            .get_or_insert_with(|| CharCursor::new(chunk.encoding));
+
+        let chunk = if self.offsets_only {
+            ChunkOffsets::convert(chunk, code, cursor).into_py(py)
+        } else {
+            Chunk::convert(chunk, code, cursor).into_py(py)
+        };
+        Ok(Some(chunk))
+    }
+
+    /// Number of chunks left.
+    fn __length_hint__(&self) -> usize {
+        self.chunks.len()
+    }
+}
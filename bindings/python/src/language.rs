@@ -1,23 +1,200 @@
+use ::code_splitter::LanguageRegistry;
+use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
-use tree_sitter::Language as TreeSitterLanguage;
+use pyo3::types::PyCapsule;
+use std::ffi::CStr;
+use std::path::Path;
+use std::sync::OnceLock;
+use tree_sitter::{ffi::TSLanguage, LANGUAGE_VERSION, MIN_COMPATIBLE_LANGUAGE_VERSION};
 
-#[pyclass(eq)]
+use crate::error::LanguageError;
+
+/// A language with a grammar bundled in the wheel.
+#[pyclass(module = "code_splitter", eq)]
 #[derive(Clone, Copy, Debug, PartialEq)]
 #[non_exhaustive]
 pub enum Language {
+    Bash,
+    C,
+    Cpp,
+    CSharp,
+    Css,
     Golang,
+    Html,
+    Java,
+    JavaScript,
+    Json,
+    Kotlin,
     Markdown,
+    Php,
     Python,
+    Ruby,
     Rust,
+    Sql,
+    Swift,
+    Toml,
+    TypeScript,
+    Tsx,
+    Yaml,
+}
+
+impl Language {
+    const ALL: &'static [Language] = &[
+        Language::Bash,
+        Language::C,
+        Language::Cpp,
+        Language::CSharp,
+        Language::Css,
+        Language::Golang,
+        Language::Html,
+        Language::Java,
+        Language::JavaScript,
+        Language::Json,
+        Language::Kotlin,
+        Language::Markdown,
+        Language::Php,
+        Language::Python,
+        Language::Ruby,
+        Language::Rust,
+        Language::Sql,
+        Language::Swift,
+        Language::Toml,
+        Language::TypeScript,
+        Language::Tsx,
+        Language::Yaml,
+    ];
 }
 
+/// Registry of the built-in file extensions, which is built on first use.
+fn registry() -> &'static LanguageRegistry {
+    static REGISTRY: OnceLock<LanguageRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(LanguageRegistry::default)
+}
+
+#[pymethods]
 impl Language {
-    pub fn as_tree_sitter_language(&self) -> TreeSitterLanguage {
-        match self {
-            Language::Golang => tree_sitter_go::language(),
-            Language::Markdown => tree_sitter_md::language(),
-            Language::Python => tree_sitter_python::language(),
-            Language::Rust => tree_sitter_rust::language(),
+    /// Get the language of files with the extension, e.g. `"py"` or `".py"`. Raises
+    /// `ValueError` for an unknown extension.
+    #[staticmethod]
+    fn from_extension(extension: &str) -> PyResult<Language> {
+        let extension = extension.strip_prefix('.').unwrap_or(extension);
+        let path = Path::new("file").with_extension(extension);
+        registry()
+            .detect_path(&path)
+            .and_then(|name| name.parse::<code_splitter::Language>().ok())
+            .and_then(|language| {
+                Language::ALL
+                    .iter()
+                    .copied()
+                    .find(|&bundled| code_splitter::Language::from(bundled) == language)
+            })
+            .ok_or_else(|| PyValueError::new_err(format!("unknown file extension: .{extension}")))
+    }
+
+    /// Pickle the language by its variant name.
+    fn __reduce__<'py>(&self, py: Python<'py>) -> PyResult<(Bound<'py, PyAny>, PyObject)> {
+        let getattr = py.import_bound("builtins")?.getattr("getattr")?;
+        let args = (py.get_type_bound::<Language>(), format!("{self:?}"));
+        Ok((getattr, args.into_py(py)))
+    }
+}
+
+impl From<Language> for code_splitter::Language {
+    fn from(language: Language) -> Self {
+        match language {
+            Language::Bash => code_splitter::Language::Bash,
+            Language::C => code_splitter::Language::C,
+            Language::Cpp => code_splitter::Language::Cpp,
+            Language::CSharp => code_splitter::Language::CSharp,
+            Language::Css => code_splitter::Language::Css,
+            Language::Golang => code_splitter::Language::Golang,
+            Language::Html => code_splitter::Language::Html,
+            Language::Java => code_splitter::Language::Java,
+            Language::JavaScript => code_splitter::Language::JavaScript,
+            Language::Json => code_splitter::Language::Json,
+            Language::Kotlin => code_splitter::Language::Kotlin,
+            Language::Markdown => code_splitter::Language::Markdown,
+            Language::Php => code_splitter::Language::Php,
+            Language::Python => code_splitter::Language::Python,
+            Language::Ruby => code_splitter::Language::Ruby,
+            Language::Rust => code_splitter::Language::Rust,
+            Language::Sql => code_splitter::Language::Sql,
+            Language::Swift => code_splitter::Language::Swift,
+            Language::Toml => code_splitter::Language::Toml,
+            Language::TypeScript => code_splitter::Language::TypeScript,
+            Language::Tsx => code_splitter::Language::Tsx,
+            Language::Yaml => code_splitter::Language::Yaml,
         }
     }
 }
+
+/// Name of the capsules holding a `TSLanguage` pointer, as exposed by `tree_sitter_<lang>`
+/// packages and accepted by py-tree-sitter.
+const CAPSULE_NAME: &CStr = c"tree_sitter.Language";
+
+/// A tree-sitter grammar given from Python: a bundled [`Language`], or the capsule returned by
+/// `language()` in the `tree_sitter_<lang>` packages.
+pub struct LanguageLike {
+    /// The grammar.
+    pub grammar: tree_sitter::Language,
+    /// The Python object the grammar was given as, kept for pickling.
+    pub object: PyObject,
+}
+
+impl<'py> FromPyObject<'py> for LanguageLike {
+    fn extract_bound(obj: &Bound<'py, PyAny>) -> PyResult<Self> {
+        let grammar = grammar(obj)?;
+        Ok(LanguageLike {
+            grammar,
+            object: obj.clone().unbind(),
+        })
+    }
+}
+
+/// Get the tree-sitter grammar of a language given from Python.
+fn grammar(obj: &Bound<'_, PyAny>) -> PyResult<tree_sitter::Language> {
+    if let Ok(language) = obj.extract::<Language>() {
+        return Ok(code_splitter::Language::from(language).tree_sitter_language());
+    }
+
+    if let Ok(capsule) = obj.downcast::<PyCapsule>() {
+        if capsule.name()? != Some(CAPSULE_NAME) {
+            return Err(PyTypeError::new_err(
+                "capsule is not a tree_sitter.Language capsule",
+            ));
+        }
+        return from_raw(capsule.pointer() as *const TSLanguage);
+    }
+
+    let ty = obj.get_type();
+    if ty.qualname()? == "Language" && ty.module()? == "tree_sitter" {
+        // py-tree-sitter has no documented way to get the `TSLanguage` pointer of a language
+        return Err(PyTypeError::new_err(
+            "tree_sitter.Language objects are not supported, pass the capsule returned by the \
+             grammar package's language() instead",
+        ));
+    }
+
+    Err(PyTypeError::new_err(format!(
+        "expected a Language or a tree_sitter.Language capsule, got {}",
+        ty.qualname()?
+    )))
+}
+
+/// Wrap a `TSLanguage` pointer, checking that its ABI version is supported.
+fn from_raw(raw: *const TSLanguage) -> PyResult<tree_sitter::Language> {
+    if raw.is_null() {
+        return Err(LanguageError::new_err("null tree-sitter language"));
+    }
+    // SAFETY: the pointer comes from a capsule named by the tree-sitter convention, whose
+    // grammar package keeps the grammar's shared library loaded for the rest of the process
+    let language = unsafe { tree_sitter::Language::from_raw(raw) };
+
+    let version = language.version();
+    if !(MIN_COMPATIBLE_LANGUAGE_VERSION..=LANGUAGE_VERSION).contains(&version) {
+        return Err(LanguageError::new_err(format!(
+            "incompatible ABI version {version}, expected {MIN_COMPATIBLE_LANGUAGE_VERSION}..={LANGUAGE_VERSION}"
+        )));
+    }
+    Ok(language)
+}
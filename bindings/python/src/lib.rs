@@ -1,21 +1,44 @@
+// pyo3 0.22 macros expand to `PyErr::from(PyErr)` in methods returning `PyResult`, which trips
+// newer clippy releases
+#![allow(clippy::useless_conversion)]
+
 mod chunk;
-pub use chunk::Chunk;
+pub use chunk::{Chunk, ChunkOffsets};
+
+mod error;
+pub use error::{CodeSplitterError, EncodingError, LanguageError, ParseError, SizerError};
+
+mod iter;
+pub use iter::ChunkIterator;
 
 mod language;
 pub use language::Language;
 
+mod sizer;
+
 mod splitter;
-pub use splitter::{CharSplitter, HuggingfaceSplitter, TiktokenSplitter, WordSplitter};
+pub use splitter::{CharSplitter, HuggingfaceSplitter, PySplitter, TiktokenSplitter, WordSplitter};
 
 use pyo3::prelude::*;
 
 #[pymodule]
 fn code_splitter(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<Chunk>()?;
+    m.add_class::<ChunkIterator>()?;
+    m.add_class::<ChunkOffsets>()?;
     m.add_class::<Language>()?;
     m.add_class::<CharSplitter>()?;
     m.add_class::<HuggingfaceSplitter>()?;
+    m.add_class::<PySplitter>()?;
     m.add_class::<TiktokenSplitter>()?;
     m.add_class::<WordSplitter>()?;
+    m.add(
+        "CodeSplitterError",
+        m.py().get_type_bound::<CodeSplitterError>(),
+    )?;
+    m.add("LanguageError", m.py().get_type_bound::<LanguageError>())?;
+    m.add("ParseError", m.py().get_type_bound::<ParseError>())?;
+    m.add("EncodingError", m.py().get_type_bound::<EncodingError>())?;
+    m.add("SizerError", m.py().get_type_bound::<SizerError>())?;
     Ok(())
 }
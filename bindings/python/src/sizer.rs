@@ -0,0 +1,41 @@
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+
+use ::code_splitter::{Error, Result, Sizer};
+
+/// A sizer backed by a Python callable, or by the `size` method of a Python object.
+pub struct PySizer {
+    /// Callable taking a text and returning its size.
+    size: PyObject,
+}
+
+impl PySizer {
+    /// Wrap an object with a `size(text) -> int` method, or a callable `(text) -> int`.
+    pub fn new(sizer: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let size = match sizer.getattr("size") {
+            Ok(size) if size.is_callable() => size,
+            _ if sizer.is_callable() => sizer.clone(),
+            _ => {
+                return Err(PyTypeError::new_err(
+                    "sizer must be callable or have a size(text) method",
+                ))
+            }
+        };
+        Ok(Self {
+            size: size.unbind(),
+        })
+    }
+}
+
+impl Sizer for PySizer {
+    /// Call the Python sizer with the GIL held. Exceptions it raises are kept in the error, so
+    /// that they reach the Python caller unchanged.
+    fn size(&self, text: &str) -> Result<usize> {
+        Python::with_gil(|py| {
+            self.size
+                .call1(py, (text,))
+                .and_then(|size| size.extract::<usize>(py))
+                .map_err(|e| Error::Sizer(e.into()))
+        })
+    }
+}
@@ -1,122 +1,461 @@
+use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
-use pyo3::types::PyBytes;
-use tiktoken_rs::{cl100k_base, CoreBPE};
-use tokenizers::Tokenizer;
+use pyo3::types::{PyBytes, PyString, PyType};
+use std::fs;
+use std::io;
+use std::num::NonZeroUsize;
+use std::panic;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
 
 use crate::chunk::Chunk;
-use crate::language::Language;
-use ::code_splitter::{CharCounter, Sizer, Splitter, WordCounter};
+use crate::error::to_py_err;
+use crate::iter::ChunkIterator;
+use crate::language::{Language, LanguageLike};
+use crate::sizer::PySizer;
+use ::code_splitter::{
+    CharCounter, HuggingfaceCounter, Sizer, Splitter, TiktokenCounter, WordCounter,
+};
 
-struct GenericSplitter<T: Sizer> {
-    splitter: Splitter<T>,
+struct GenericSplitter {
+    splitter: Splitter<Box<dyn Sizer + Send + Sync>>,
+    /// The language as given from Python, kept for pickling.
+    language: PyObject,
+    max_size: usize,
 }
 
-impl<T: Sizer> GenericSplitter<T> {
-    fn new(language: Language, max_size: usize, sizer: T) -> PyResult<Self> {
-        let splitter = Splitter::new(language.as_tree_sitter_language(), sizer)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?
+impl GenericSplitter {
+    fn new<T>(language: LanguageLike, max_size: usize, sizer: T) -> PyResult<Self>
+    where
+        T: Sizer + Send + Sync + 'static,
+    {
+        let sizer: Box<dyn Sizer + Send + Sync> = Box::new(sizer);
+        let splitter = Splitter::new(language.grammar, sizer)
+            .map_err(to_py_err)?
             .with_max_size(max_size);
 
-        Ok(GenericSplitter { splitter })
+        Ok(GenericSplitter {
+            splitter,
+            language: language.object,
+            max_size,
+        })
     }
 
-    fn split(&self, code: &Bound<'_, PyBytes>) -> PyResult<Vec<PyObject>> {
-        let code_bytes = code.as_bytes();
-        let chunks = self
-            .splitter
-            .split(code_bytes)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
-
-        Python::with_gil(|py| {
-            chunks
-                .into_iter()
-                .map(|chunk| {
-                    let chunk = Chunk {
-                        subtree: chunk.subtree.to_string(),
-                        start: chunk.range.start_point.row,
-                        end: chunk.range.end_point.row,
-                        size: chunk.size,
-                        text: chunk.utf8_lossy(code_bytes),
-                    };
-                    Ok(chunk.into_py(py))
-                })
-                .collect::<Result<Vec<PyObject>, PyErr>>()
-        })
+    /// Get the language and maximum size, the leading arguments to rebuild the splitter with.
+    /// Fails for a language given as a tree-sitter capsule, which cannot be pickled.
+    fn args(&self, py: Python<'_>) -> PyResult<(PyObject, usize)> {
+        if !self.language.bind(py).is_instance_of::<Language>() {
+            return Err(PyTypeError::new_err(
+                "cannot pickle a splitter whose `language` is a tree-sitter capsule, \
+                 create it with a code_splitter.Language to pickle it",
+            ));
+        }
+        Ok((self.language.clone_ref(py), self.max_size))
+    }
+
+    fn split(&self, py: Python<'_>, code: &Bound<'_, PyAny>) -> PyResult<Vec<Chunk>> {
+        let code = code_bytes(code)?;
+        // Parse and split without the GIL, so that other Python threads can run meanwhile
+        py.allow_threads(|| self.split_bytes(code))
     }
-}
 
-#[pyclass]
-pub struct CharSplitter(GenericSplitter<CharCounter>);
+    fn iter_split(
+        &self,
+        py: Python<'_>,
+        code: &Bound<'_, PyAny>,
+        offsets_only: bool,
+    ) -> PyResult<ChunkIterator> {
+        let bytes = code_bytes(code)?;
+        let chunks = py
+            .allow_threads(|| self.splitter.split(bytes))
+            .map_err(to_py_err)?;
+        Ok(ChunkIterator::new(
+            code.clone().unbind(),
+            chunks,
+            offsets_only,
+        ))
+    }
+
+    fn split_many(
+        &self,
+        py: Python<'_>,
+        codes: Vec<Bound<'_, PyAny>>,
+        return_exceptions: bool,
+    ) -> PyResult<Vec<PyObject>> {
+        let codes = codes.iter().map(code_bytes).collect::<PyResult<Vec<_>>>()?;
+        let results = py.allow_threads(|| parallel_map(&codes, |code| self.split_bytes(code)));
+        collect_results(py, results, return_exceptions)
+    }
 
-#[pymethods]
-impl CharSplitter {
-    #[new]
-    #[pyo3(signature = (language, max_size))]
-    fn new(language: Language, max_size: usize) -> PyResult<Self> {
-        let splitter = GenericSplitter::new(language, max_size, CharCounter)?;
-        Ok(CharSplitter(splitter))
+    fn split_files(
+        &self,
+        py: Python<'_>,
+        paths: Vec<PathBuf>,
+        return_exceptions: bool,
+    ) -> PyResult<Vec<PyObject>> {
+        let results = py.allow_threads(|| {
+            parallel_map(&paths, |path| {
+                let code = fs::read(path).map_err(|e| {
+                    // Keep the kind of the error, so that e.g. FileNotFoundError is raised
+                    io::Error::new(e.kind(), format!("{}: {e}", path.display()))
+                })?;
+                self.split_bytes(&code)
+            })
+        });
+        collect_results(py, results, return_exceptions)
     }
 
-    fn split(&self, code: &Bound<'_, PyBytes>) -> PyResult<Vec<PyObject>> {
-        self.0.split(code)
+    fn split_bytes(&self, code: &[u8]) -> PyResult<Vec<Chunk>> {
+        let chunks = self.splitter.split(code).map_err(to_py_err)?;
+        Ok(Chunk::from_chunks(chunks, code))
+    }
+}
+
+/// Get the bytes of code given as `bytes`, or the UTF-8 bytes of code given as `str`.
+pub fn code_bytes<'a>(code: &'a Bound<'_, PyAny>) -> PyResult<&'a [u8]> {
+    match code.downcast::<PyString>() {
+        Ok(text) => Ok(text.to_str()?.as_bytes()),
+        Err(_) => Ok(code.downcast::<PyBytes>()?.as_bytes()),
     }
 }
 
-#[pyclass]
-pub struct WordSplitter(GenericSplitter<WordCounter>);
+/// Apply the function to every input on a pool of threads, returning the outputs in the order
+/// of the inputs. Threads take the next input as soon as they're done, so that a few large
+/// inputs don't hold up the rest.
+fn parallel_map<I, O, F>(inputs: &[I], f: F) -> Vec<O>
+where
+    I: Sync,
+    O: Send,
+    F: Fn(&I) -> O + Sync,
+{
+    let threads = thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(inputs.len());
+    let next = AtomicUsize::new(0);
+
+    let mut outputs = thread::scope(|scope| {
+        (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut outputs = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(input) = inputs.get(i) else {
+                            break outputs;
+                        };
+                        outputs.push((i, f(input)));
+                    }
+                })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap_or_else(|e| panic::resume_unwind(e)))
+            .collect::<Vec<_>>()
+    });
+
+    outputs.sort_unstable_by_key(|&(i, _)| i);
+    outputs.into_iter().map(|(_, output)| output).collect()
+}
+
+/// Convert the results of several inputs to Python objects. Raises the error of the first
+/// failed input, unless `return_exceptions` is set, in which case the exception takes the
+/// place of the input's chunks.
+fn collect_results(
+    py: Python<'_>,
+    results: Vec<PyResult<Vec<Chunk>>>,
+    return_exceptions: bool,
+) -> PyResult<Vec<PyObject>> {
+    results
+        .into_iter()
+        .map(|result| match result {
+            Ok(chunks) => Ok(chunks.into_py(py)),
+            Err(e) if return_exceptions => Ok(e.into_value(py).into_py(py)),
+            Err(e) => Err(e),
+        })
+        .collect()
+}
+
+/// Implement the Python methods of a splitter class, which has its `GenericSplitter` in a
+/// `splitter` field, adding the splitting methods shared by all splitters to the methods given.
+macro_rules! splitter_methods {
+    (impl $name:ident { $($methods:tt)* }) => {
+        #[pymethods]
+        impl $name {
+            $($methods)*
+
+            /// Split the code, given as `bytes` or `str`, into chunks.
+            fn split(&self, py: Python<'_>, code: &Bound<'_, PyAny>) -> PyResult<Vec<Chunk>> {
+                self.splitter.split(py, code)
+            }
+
+            /// Split the code into chunks lazily, creating each chunk and copying its text only
+            /// when the iterator reaches it. With `offsets_only`, `ChunkOffsets` without the text
+            /// are yielded instead.
+            #[pyo3(signature = (code, offsets_only=false))]
+            fn iter_split(
+                &self,
+                py: Python<'_>,
+                code: &Bound<'_, PyAny>,
+                offsets_only: bool,
+            ) -> PyResult<ChunkIterator> {
+                self.splitter.iter_split(py, code, offsets_only)
+            }
+
+            /// Split several codes in parallel, returning the chunks of each.
+            #[pyo3(signature = (codes, return_exceptions=false))]
+            fn split_many(
+                &self,
+                py: Python<'_>,
+                codes: Vec<Bound<'_, PyAny>>,
+                return_exceptions: bool,
+            ) -> PyResult<Vec<PyObject>> {
+                self.splitter.split_many(py, codes, return_exceptions)
+            }
 
-#[pymethods]
-impl WordSplitter {
-    #[new]
-    #[pyo3(signature = (language, max_size))]
-    fn new(language: Language, max_size: usize) -> PyResult<Self> {
-        let splitter = GenericSplitter::new(language, max_size, WordCounter)?;
-        Ok(WordSplitter(splitter))
+            /// Read and split several files in parallel, returning the chunks of each.
+            #[pyo3(signature = (paths, return_exceptions=false))]
+            fn split_files(
+                &self,
+                py: Python<'_>,
+                paths: Vec<PathBuf>,
+                return_exceptions: bool,
+            ) -> PyResult<Vec<PyObject>> {
+                self.splitter.split_files(py, paths, return_exceptions)
+            }
+        }
+    };
+}
+
+/// Splitter that measures chunks with a Python sizer: a callable `(text) -> int`, or an object
+/// with a `size(text) -> int` method.
+#[pyclass(module = "code_splitter", name = "Splitter")]
+pub struct PySplitter {
+    splitter: GenericSplitter,
+    /// The sizer as given from Python, kept for pickling.
+    sizer: PyObject,
+}
+
+splitter_methods! {
+    impl PySplitter {
+        #[new]
+        #[pyo3(signature = (language, max_size, sizer))]
+        fn new(
+            language: LanguageLike,
+            max_size: usize,
+            sizer: &Bound<'_, PyAny>,
+        ) -> PyResult<Self> {
+            let splitter = GenericSplitter::new(language, max_size, PySizer::new(sizer)?)?;
+            Ok(PySplitter {
+                splitter,
+                sizer: sizer.clone().unbind(),
+            })
+        }
+
+        /// Pickle the splitter by its arguments. The sizer must be picklable too, and the
+        /// `language` cannot be a tree-sitter capsule.
+        fn __reduce__<'py>(&self, py: Python<'py>) -> PyResult<(Bound<'py, PyType>, PyObject)> {
+            let (language, max_size) = self.splitter.args(py)?;
+            let args = (language, max_size, self.sizer.clone_ref(py));
+            Ok((py.get_type_bound::<Self>(), args.into_py(py)))
+        }
     }
+}
 
-    fn split(&self, code: &Bound<'_, PyBytes>) -> PyResult<Vec<PyObject>> {
-        self.0.split(code)
+#[pyclass(module = "code_splitter")]
+pub struct CharSplitter {
+    splitter: GenericSplitter,
+}
+
+splitter_methods! {
+    impl CharSplitter {
+        #[new]
+        #[pyo3(signature = (language, max_size))]
+        fn new(language: LanguageLike, max_size: usize) -> PyResult<Self> {
+            let splitter = GenericSplitter::new(language, max_size, CharCounter)?;
+            Ok(CharSplitter { splitter })
+        }
+
+        /// Pickle the splitter by its arguments. The `language` cannot be a tree-sitter capsule.
+        fn __reduce__<'py>(&self, py: Python<'py>) -> PyResult<(Bound<'py, PyType>, PyObject)> {
+            let args = self.splitter.args(py)?;
+            Ok((py.get_type_bound::<Self>(), args.into_py(py)))
+        }
     }
 }
 
-#[pyclass]
-pub struct TiktokenSplitter(GenericSplitter<CoreBPE>);
+#[pyclass(module = "code_splitter")]
+pub struct WordSplitter {
+    splitter: GenericSplitter,
+}
+
+splitter_methods! {
+    impl WordSplitter {
+        #[new]
+        #[pyo3(signature = (language, max_size))]
+        fn new(language: LanguageLike, max_size: usize) -> PyResult<Self> {
+            let splitter = GenericSplitter::new(language, max_size, WordCounter)?;
+            Ok(WordSplitter { splitter })
+        }
 
-#[pymethods]
-impl TiktokenSplitter {
-    #[new]
-    #[pyo3(signature = (language, max_size))]
-    fn new(language: Language, max_size: usize) -> PyResult<Self> {
-        let bpe = cl100k_base()
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
-        let splitter = GenericSplitter::new(language, max_size, bpe)?;
-        Ok(TiktokenSplitter(splitter))
+        /// Pickle the splitter by its arguments. The `language` cannot be a tree-sitter capsule.
+        fn __reduce__<'py>(&self, py: Python<'py>) -> PyResult<(Bound<'py, PyType>, PyObject)> {
+            let args = self.splitter.args(py)?;
+            Ok((py.get_type_bound::<Self>(), args.into_py(py)))
+        }
     }
+}
+
+#[pyclass(module = "code_splitter")]
+pub struct TiktokenSplitter {
+    splitter: GenericSplitter,
+    /// Name of the encoding, which the model name given to the constructor resolves to.
+    encoding: &'static str,
+}
 
-    fn split(&self, code: &Bound<'_, PyBytes>) -> PyResult<Vec<PyObject>> {
-        self.0.split(code)
+splitter_methods! {
+    impl TiktokenSplitter {
+        /// Split by tokens of a tiktoken encoding, chosen by `encoding` name (e.g. `o200k_base`) or
+        /// by OpenAI `model` name (e.g. `gpt-4o`). Defaults to `cl100k_base`.
+        #[new]
+        #[pyo3(signature = (language, max_size, encoding=None, model=None))]
+        fn new(
+            language: LanguageLike,
+            max_size: usize,
+            encoding: Option<&str>,
+            model: Option<&str>,
+        ) -> PyResult<Self> {
+            let counter = match (encoding, model) {
+                (Some(_), Some(_)) => {
+                    return Err(PyValueError::new_err(
+                        "encoding and model cannot both be given",
+                    ))
+                }
+                (_, Some(model)) => TiktokenCounter::for_model(model),
+                (encoding, None) => TiktokenCounter::new(encoding.unwrap_or("cl100k_base")),
+            }
+            .map_err(to_py_err)?;
+            let encoding = counter.encoding();
+            let splitter = GenericSplitter::new(language, max_size, counter)?;
+            Ok(TiktokenSplitter { splitter, encoding })
+        }
+
+        /// Pickle the splitter by its arguments, with the model resolved to its encoding. The
+        /// `language` cannot be a tree-sitter capsule.
+        fn __reduce__<'py>(&self, py: Python<'py>) -> PyResult<(Bound<'py, PyType>, PyObject)> {
+            let (language, max_size) = self.splitter.args(py)?;
+            let args = (language, max_size, self.encoding);
+            Ok((py.get_type_bound::<Self>(), args.into_py(py)))
+        }
     }
 }
 
-#[pyclass]
-pub struct HuggingfaceSplitter(GenericSplitter<Tokenizer>);
+#[pyclass(module = "code_splitter")]
+pub struct HuggingfaceSplitter {
+    splitter: GenericSplitter,
+    counter: Arc<HuggingfaceCounter>,
+    /// Name of the pretrained tokenizer, if it was downloaded from the HuggingFace Hub.
+    pretrained: Option<String>,
+    add_special_tokens: bool,
+}
 
-#[pymethods]
 impl HuggingfaceSplitter {
-    #[new]
-    #[pyo3(signature = (language, max_size, pretrained_model_name_or_path))]
-    fn new(
-        language: Language,
+    fn with_counter(
+        language: LanguageLike,
         max_size: usize,
-        pretrained_model_name_or_path: &str,
+        counter: code_splitter::Result<HuggingfaceCounter>,
+        pretrained: Option<&str>,
+        add_special_tokens: bool,
     ) -> PyResult<Self> {
-        let tokenizer = Tokenizer::from_pretrained(pretrained_model_name_or_path, None)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
-        let splitter = GenericSplitter::new(language, max_size, tokenizer)?;
-        Ok(HuggingfaceSplitter(splitter))
+        let counter = Arc::new(
+            counter
+                .map_err(to_py_err)?
+                .with_special_tokens(add_special_tokens),
+        );
+        let splitter = GenericSplitter::new(language, max_size, Arc::clone(&counter))?;
+        Ok(HuggingfaceSplitter {
+            splitter,
+            counter,
+            pretrained: pretrained.map(str::to_string),
+            add_special_tokens,
+        })
     }
+}
+
+splitter_methods! {
+    impl HuggingfaceSplitter {
+        /// Split by tokens of a pretrained tokenizer downloaded from the HuggingFace Hub. If
+        /// `add_special_tokens` is set, the special tokens the tokenizer adds to every input, such
+        /// as `[CLS]` and `[SEP]`, count toward `max_size`.
+        #[new]
+        #[pyo3(signature = (
+            language,
+            max_size,
+            pretrained_model_name_or_path,
+            add_special_tokens=false,
+        ))]
+        fn new(
+            language: LanguageLike,
+            max_size: usize,
+            pretrained_model_name_or_path: &str,
+            add_special_tokens: bool,
+        ) -> PyResult<Self> {
+            let counter = HuggingfaceCounter::from_pretrained(pretrained_model_name_or_path);
+            Self::with_counter(
+                language,
+                max_size,
+                counter,
+                Some(pretrained_model_name_or_path),
+                add_special_tokens,
+            )
+        }
+
+        /// Split by tokens of a tokenizer loaded from a local `tokenizer.json` file.
+        #[staticmethod]
+        #[pyo3(signature = (language, max_size, path, add_special_tokens=false))]
+        fn from_file(
+            language: LanguageLike,
+            max_size: usize,
+            path: PathBuf,
+            add_special_tokens: bool,
+        ) -> PyResult<Self> {
+            let counter = HuggingfaceCounter::from_file(path);
+            Self::with_counter(language, max_size, counter, None, add_special_tokens)
+        }
+
+        /// Split by tokens of a tokenizer loaded from the bytes of a `tokenizer.json` file.
+        #[staticmethod]
+        #[pyo3(signature = (language, max_size, data, add_special_tokens=false))]
+        fn from_bytes(
+            language: LanguageLike,
+            max_size: usize,
+            data: &[u8],
+            add_special_tokens: bool,
+        ) -> PyResult<Self> {
+            let counter = HuggingfaceCounter::from_bytes(data);
+            Self::with_counter(language, max_size, counter, None, add_special_tokens)
+        }
+
+        /// Pickle the splitter by its arguments. Pretrained tokenizers are loaded again by name,
+        /// other tokenizers are pickled as their `tokenizer.json`, since the file may not exist
+        /// where the splitter is unpickled. The `language` cannot be a tree-sitter capsule.
+        fn __reduce__<'py>(&self, py: Python<'py>) -> PyResult<(Bound<'py, PyAny>, PyObject)> {
+            let (language, max_size) = self.splitter.args(py)?;
+            let cls = py.get_type_bound::<Self>();
+            if let Some(pretrained) = &self.pretrained {
+                let args = (language, max_size, pretrained, self.add_special_tokens);
+                return Ok((cls.into_any(), args.into_py(py)));
+            }
 
-    fn split(&self, code: &Bound<'_, PyBytes>) -> PyResult<Vec<PyObject>> {
-        self.0.split(code)
+            let json = self
+                .counter
+                .inner()
+                .to_string(false)
+                .map_err(|e| to_py_err(code_splitter::Error::Sizer(e)))?;
+            let data = PyBytes::new_bound(py, json.as_bytes());
+            let args = (language, max_size, data, self.add_special_tokens);
+            Ok((cls.getattr("from_bytes")?, args.into_py(py)))
+        }
     }
 }
@@ -0,0 +1,71 @@
+from code_splitter import CharSplitter, Chunk, Language
+
+PYTHON_INPUT = """# Größen berechnen 📐
+def area(w, h):
+    return w * h
+
+
+def perimeter(w, h):
+    return 2 * (w + h)
+"""
+
+
+def split(code: bytes | str) -> list[Chunk]:
+    splitter = CharSplitter(language=Language.Python, max_size=40)
+    return splitter.split(code)
+
+
+def test_split_str() -> None:
+    chunks = split(PYTHON_INPUT)
+
+    assert len(chunks) > 1
+    assert chunks == split(PYTHON_INPUT.encode())
+    for chunk in chunks:
+        assert PYTHON_INPUT[chunk.start_char : chunk.end_char] == chunk.text
+        assert PYTHON_INPUT.encode()[chunk.start_byte : chunk.end_byte] == chunk.text.encode()
+
+
+def test_columns() -> None:
+    lines = PYTHON_INPUT.split("\n")
+    for chunk in split(PYTHON_INPUT):
+        assert lines[chunk.start][chunk.start_column :].startswith(chunk.text.split("\n")[0])
+        assert lines[chunk.end][: chunk.end_column].endswith(chunk.text.split("\n")[-1])
+
+
+def test_non_ascii_offsets() -> None:
+    first = split(PYTHON_INPUT)[0]
+
+    assert first.text.startswith("# Größen berechnen 📐")
+    # "ö", "ß" and "📐" take more than one byte each
+    assert first.end_byte > first.end_char
+
+
+def test_repr_and_dict() -> None:
+    chunk = split(PYTHON_INPUT)[1]
+
+    assert repr(chunk).startswith(f"Chunk(start={chunk.start}, end={chunk.end},")
+    fields = chunk.to_dict()
+    assert fields["text"] == chunk.text
+    assert fields["start_char"] == chunk.start_char
+    assert fields["tokenizer"] is None
+    assert set(fields) == {
+        "subtree",
+        "start",
+        "end",
+        "start_byte",
+        "end_byte",
+        "start_char",
+        "end_char",
+        "start_column",
+        "end_column",
+        "size",
+        "text",
+        "tokenizer",
+    }
+
+
+def test_eq() -> None:
+    first, second = split(PYTHON_INPUT)[:2]
+
+    assert first == split(PYTHON_INPUT)[0]
+    assert first != second
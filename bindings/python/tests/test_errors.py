@@ -0,0 +1,42 @@
+import pytest
+from code_splitter import (
+    CodeSplitterError,
+    EncodingError,
+    HuggingfaceSplitter,
+    Language,
+    LanguageError,
+    ParseError,
+    SizerError,
+    TiktokenSplitter,
+)
+
+
+@pytest.mark.parametrize(
+    "error", [LanguageError, ParseError, EncodingError, SizerError]
+)
+def test_error_hierarchy(error: type) -> None:
+    assert issubclass(error, CodeSplitterError)
+    assert issubclass(error, RuntimeError)
+
+
+def test_sizer_error() -> None:
+    with pytest.raises(SizerError):
+        HuggingfaceSplitter(
+            language=Language.Markdown,
+            max_size=128,
+            pretrained_model_name_or_path="does-not-exist/tokenizer",
+        )
+
+
+def test_unknown_tiktoken_encoding() -> None:
+    with pytest.raises(ValueError, match="nope"):
+        TiktokenSplitter(language=Language.Markdown, max_size=128, encoding="nope")
+    with pytest.raises(ValueError, match="nope"):
+        TiktokenSplitter(language=Language.Markdown, max_size=128, model="nope")
+    with pytest.raises(ValueError):
+        TiktokenSplitter(
+            language=Language.Markdown,
+            max_size=128,
+            encoding="cl100k_base",
+            model="gpt-4o",
+        )
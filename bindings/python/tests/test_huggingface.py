@@ -0,0 +1,57 @@
+from pathlib import Path
+
+import pytest
+from code_splitter import HuggingfaceSplitter, Language, SizerError
+
+# A small word-level tokenizer that adds [CLS] and [SEP] around every input
+TOKENIZER_FILE = Path(__file__).parents[3] / "tests" / "testdata" / "tokenizer.json"
+
+MARKDOWN_INPUT = b"""# hello
+
+hello, world!
+
+hello world hello world
+"""
+
+
+def test_from_file() -> None:
+    splitter = HuggingfaceSplitter.from_file(
+        language=Language.Markdown, max_size=4, path=TOKENIZER_FILE
+    )
+    chunks = splitter.split(MARKDOWN_INPUT)
+
+    assert [chunk.size for chunk in chunks] == [2, 4, 4]
+    assert all(chunk.tokenizer is None for chunk in chunks)
+
+
+def test_from_bytes() -> None:
+    splitter = HuggingfaceSplitter.from_bytes(
+        language=Language.Markdown, max_size=4, data=TOKENIZER_FILE.read_bytes()
+    )
+    chunks = splitter.split(MARKDOWN_INPUT)
+
+    assert [chunk.size for chunk in chunks] == [2, 4, 4]
+
+
+def test_add_special_tokens() -> None:
+    splitter = HuggingfaceSplitter.from_file(
+        language=Language.Markdown,
+        max_size=6,
+        path=str(TOKENIZER_FILE),
+        add_special_tokens=True,
+    )
+    chunks = splitter.split(MARKDOWN_INPUT)
+
+    # [CLS] and [SEP] count toward every chunk
+    assert [chunk.size for chunk in chunks] == [4, 6, 6]
+
+
+def test_invalid_tokenizer() -> None:
+    with pytest.raises(SizerError):
+        HuggingfaceSplitter.from_bytes(
+            language=Language.Markdown, max_size=4, data=b"{}"
+        )
+    with pytest.raises(SizerError):
+        HuggingfaceSplitter.from_file(
+            language=Language.Markdown, max_size=4, path="does/not/exist.json"
+        )
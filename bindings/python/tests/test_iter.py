@@ -0,0 +1,78 @@
+import pytest
+from code_splitter import CharSplitter, ChunkOffsets, Language, TiktokenSplitter
+
+PYTHON_INPUT = """# Größen berechnen 📐
+def area(w, h):
+    return w * h
+
+
+def perimeter(w, h):
+    return 2 * (w + h)
+
+
+class Rectangle:
+    def __init__(self, w, h):
+        self.w = w
+        self.h = h
+"""
+
+
+@pytest.mark.parametrize("code", [PYTHON_INPUT, PYTHON_INPUT.encode()])
+def test_iter_split(code: str | bytes) -> None:
+    splitter = CharSplitter(language=Language.Python, max_size=40)
+    chunks = splitter.iter_split(code)
+
+    assert iter(chunks) is chunks
+    assert chunks.__length_hint__() > 1
+    assert list(chunks) == splitter.split(code)
+    assert chunks.__length_hint__() == 0
+    with pytest.raises(StopIteration):
+        next(chunks)
+
+
+def test_iter_split_lazily() -> None:
+    splitter = CharSplitter(language=Language.Python, max_size=40)
+    chunks = splitter.iter_split(PYTHON_INPUT)
+    total = chunks.__length_hint__()
+
+    first = next(chunks)
+    assert first.text.startswith("# Größen berechnen 📐")
+    assert chunks.__length_hint__() == total - 1
+
+
+@pytest.mark.parametrize("code", [PYTHON_INPUT, PYTHON_INPUT.encode()])
+def test_offsets_only(code: str | bytes) -> None:
+    splitter = TiktokenSplitter(language=Language.Python, max_size=16)
+    offsets = list(splitter.iter_split(code, offsets_only=True))
+    chunks = splitter.split(code)
+
+    assert len(offsets) == len(chunks)
+    for offset, chunk in zip(offsets, chunks):
+        assert isinstance(offset, ChunkOffsets)
+        assert not hasattr(offset, "text")
+        assert (offset.start_byte, offset.end_byte) == (chunk.start_byte, chunk.end_byte)
+        assert (offset.start_char, offset.end_char) == (chunk.start_char, chunk.end_char)
+        assert (offset.size, offset.tokenizer) == (chunk.size, chunk.tokenizer)
+        # The offsets slice the text out of the code it was given as
+        if isinstance(code, str):
+            assert code[offset.start_char : offset.end_char] == chunk.text
+        else:
+            assert code[offset.start_byte : offset.end_byte] == chunk.text.encode()
+
+
+def test_offsets_repr() -> None:
+    splitter = CharSplitter(language=Language.Python, max_size=40)
+    offsets = next(splitter.iter_split(PYTHON_INPUT, offsets_only=True))
+
+    assert repr(offsets).startswith("ChunkOffsets(start=0, end=")
+    assert offsets == ChunkOffsets(
+        offsets.start,
+        offsets.end,
+        offsets.start_byte,
+        offsets.end_byte,
+        offsets.start_char,
+        offsets.end_char,
+        offsets.start_column,
+        offsets.end_column,
+        offsets.size,
+    )
@@ -0,0 +1,55 @@
+import ctypes
+
+import pytest
+from code_splitter import CharSplitter, Language
+
+PYTHON_INPUT = b"""def add(a, b):
+    return a + b
+
+
+class Calculator:
+    def mul(self, a, b):
+        return a * b
+"""
+
+
+def make_capsule(name: bytes) -> object:
+    new_capsule = ctypes.pythonapi.PyCapsule_New
+    new_capsule.restype = ctypes.py_object
+    new_capsule.argtypes = [ctypes.c_void_p, ctypes.c_char_p, ctypes.c_void_p]
+    # The capsule is rejected by name, so its pointer is never read
+    return new_capsule(ctypes.c_void_p(1), name, None)
+
+
+def test_tree_sitter_capsule() -> None:
+    tree_sitter_python = pytest.importorskip("tree_sitter_python")
+    splitter = CharSplitter(language=tree_sitter_python.language(), max_size=30)
+    chunks = splitter.split(PYTHON_INPUT)
+
+    assert " ".join(chunk.text for chunk in chunks).split() == PYTHON_INPUT.decode().split()
+    assert all(chunk.size <= 30 for chunk in chunks)
+
+
+def test_tree_sitter_language_rejected() -> None:
+    tree_sitter = pytest.importorskip("tree_sitter")
+    tree_sitter_python = pytest.importorskip("tree_sitter_python")
+    language = tree_sitter.Language(tree_sitter_python.language())
+
+    with pytest.raises(TypeError, match="pass the capsule"):
+        CharSplitter(language=language, max_size=30)
+
+
+def test_foreign_capsule() -> None:
+    with pytest.raises(TypeError, match="tree_sitter.Language capsule"):
+        CharSplitter(language=make_capsule(b"numpy.ndarray"), max_size=30)
+
+
+@pytest.mark.parametrize("language", [None, 42, "python"])
+def test_invalid_language(language: object) -> None:
+    with pytest.raises(TypeError):
+        CharSplitter(language=language, max_size=30)
+
+
+def test_bundled_language() -> None:
+    chunks = CharSplitter(language=Language.Python, max_size=30).split(PYTHON_INPUT)
+    assert chunks[0].text.startswith("def add")
@@ -0,0 +1,90 @@
+from pathlib import Path
+from typing import Protocol
+
+import pytest
+from code_splitter import (
+    CharSplitter,
+    Chunk,
+    Language,
+    TiktokenSplitter,
+    WordSplitter,
+)
+
+TESTDATA = Path(__file__).parent / "testdata"
+
+LANGUAGES = [
+    (Language.Bash, "rectangle.sh"),
+    (Language.C, "rectangle.c"),
+    (Language.Cpp, "rectangle.cpp"),
+    (Language.CSharp, "Rectangle.cs"),
+    (Language.Css, "rectangle.css"),
+    (Language.Golang, "rectangle.go"),
+    (Language.Html, "rectangle.html"),
+    (Language.Java, "Rectangle.java"),
+    (Language.JavaScript, "rectangle.js"),
+    (Language.Json, "rectangle.json"),
+    (Language.Kotlin, "Rectangle.kt"),
+    (Language.Markdown, "rectangle.md"),
+    (Language.Php, "rectangle.php"),
+    (Language.Python, "rectangle.py"),
+    (Language.Ruby, "rectangle.rb"),
+    (Language.Rust, "rectangle.rs"),
+    (Language.Sql, "rectangle.sql"),
+    (Language.Swift, "rectangle.swift"),
+    (Language.Toml, "rectangle.toml"),
+    (Language.TypeScript, "rectangle.ts"),
+    (Language.Tsx, "rectangle.tsx"),
+    (Language.Yaml, "rectangle.yaml"),
+]
+
+
+class Splitter(Protocol):
+    def split(self, code: bytes) -> list[Chunk]: ...
+
+
+@pytest.mark.parametrize(("language", "filename"), LANGUAGES)
+@pytest.mark.parametrize(
+    ("splitter_class", "max_size"),
+    [(CharSplitter, 128), (WordSplitter, 16), (TiktokenSplitter, 32)],
+)
+def test_split_language(
+    language: Language, filename: str, splitter_class: type, max_size: int
+) -> None:
+    code = (TESTDATA / filename).read_bytes()
+    splitter: Splitter = splitter_class(language=language, max_size=max_size)
+    chunks = splitter.split(code)
+
+    # The sample files are larger than one chunk
+    assert len(chunks) > 1
+    end = 0
+    for chunk in chunks:
+        assert 0 < chunk.size <= max_size
+        assert code[chunk.start_byte : chunk.end_byte].decode() == chunk.text
+        assert chunk.start_byte >= end
+        end = chunk.end_byte
+
+
+@pytest.mark.parametrize(("language", "filename"), LANGUAGES)
+def test_from_extension(language: Language, filename: str) -> None:
+    assert Language.from_extension(Path(filename).suffix) == language
+
+
+@pytest.mark.parametrize(
+    ("extension", "language"),
+    [
+        ("py", Language.Python),
+        ("PY", Language.Python),
+        ("h", Language.C),
+        ("hpp", Language.Cpp),
+        ("yml", Language.Yaml),
+        ("mjs", Language.JavaScript),
+    ],
+)
+def test_from_extension_aliases(extension: str, language: Language) -> None:
+    assert Language.from_extension(extension) == language
+
+
+@pytest.mark.parametrize("extension", ["", "xyz", ".klingon"])
+def test_from_extension_unknown(extension: str) -> None:
+    with pytest.raises(ValueError, match="unknown file extension"):
+        Language.from_extension(extension)
@@ -95,3 +95,29 @@ def test_split_markdown(splitter: Splitter, max_size: int) -> None:
     # Check the size of the chunks is less than the max size
     for chunk in chunks:
         assert chunk.size <= max_size
+
+
+@pytest.mark.parametrize(
+    ("kwargs", "encoding"),
+    [
+        ({}, "cl100k_base"),
+        ({"encoding": "o200k_base"}, "o200k_base"),
+        ({"encoding": "p50k_base"}, "p50k_base"),
+        ({"model": "gpt-4o"}, "o200k_base"),
+        ({"model": "text-davinci-003"}, "p50k_base"),
+    ],
+)
+def test_tiktoken_encoding(kwargs: dict[str, str], encoding: str) -> None:
+    splitter = TiktokenSplitter(language=Language.Markdown, max_size=128, **kwargs)
+    chunks = splitter.split(MARKDOWN_INPUT)
+
+    for chunk in chunks:
+        assert chunk.size <= 128
+        assert chunk.tokenizer == encoding
+
+
+def test_char_splitter_has_no_tokenizer() -> None:
+    splitter = CharSplitter(language=Language.Markdown, max_size=512)
+    chunks = splitter.split(MARKDOWN_INPUT)
+
+    assert all(chunk.tokenizer is None for chunk in chunks)
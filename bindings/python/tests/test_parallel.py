@@ -0,0 +1,78 @@
+from concurrent.futures import ThreadPoolExecutor
+from pathlib import Path
+
+import pytest
+from code_splitter import CharSplitter, Language, Splitter
+
+CODES = [
+    b"def add(a, b):\n    return a + b\n\n\ndef sub(a, b):\n    return a - b\n",
+    "class Point:\n    def __init__(self, x, y):\n        self.x = x\n        self.y = y\n",
+    b"",
+    b"print('hello')\n" * 20,
+]
+
+
+@pytest.fixture
+def splitter() -> CharSplitter:
+    return CharSplitter(language=Language.Python, max_size=40)
+
+
+def test_split_many(splitter: CharSplitter) -> None:
+    results = splitter.split_many(CODES)
+
+    assert results == [splitter.split(code) for code in CODES]
+    assert results[2] == []
+
+
+def test_split_files(splitter: CharSplitter, tmp_path: Path) -> None:
+    paths = []
+    for i, code in enumerate(CODES):
+        path = tmp_path / f"code_{i}.py"
+        path.write_bytes(code if isinstance(code, bytes) else code.encode())
+        paths.append(path)
+
+    results = splitter.split_files(paths)
+    assert results == [splitter.split(code) for code in CODES]
+    assert splitter.split_files([str(path) for path in paths]) == results
+
+
+def test_split_files_missing(splitter: CharSplitter, tmp_path: Path) -> None:
+    path = tmp_path / "code.py"
+    path.write_bytes(CODES[0])
+    missing = tmp_path / "missing.py"
+
+    with pytest.raises(FileNotFoundError):
+        splitter.split_files([path, missing])
+
+    results = splitter.split_files([path, missing], return_exceptions=True)
+    assert results[0] == splitter.split(CODES[0])
+    assert isinstance(results[1], FileNotFoundError)
+    assert "missing.py" in str(results[1])
+
+
+def test_split_many_python_sizer_errors() -> None:
+    def size(text: str) -> int:
+        if "sub" in text:
+            raise ValueError("no subtraction")
+        return len(text)
+
+    splitter = Splitter(language=Language.Python, max_size=40, sizer=size)
+    results = splitter.split_many(CODES, return_exceptions=True)
+
+    assert isinstance(results[0], ValueError)
+    assert results[1] == CharSplitter(Language.Python, 40).split(CODES[1])
+    with pytest.raises(ValueError):
+        splitter.split_many(CODES)
+
+
+def test_split_in_threads(splitter: CharSplitter) -> None:
+    codes = CODES * 8
+    with ThreadPoolExecutor(max_workers=4) as executor:
+        results = list(executor.map(splitter.split, codes))
+
+    assert results == [splitter.split(code) for code in codes]
+
+
+def test_split_many_type_error(splitter: CharSplitter) -> None:
+    with pytest.raises(TypeError):
+        splitter.split_many([1, 2])
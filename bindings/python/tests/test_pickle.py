@@ -0,0 +1,93 @@
+import pickle
+from pathlib import Path
+
+import pytest
+from code_splitter import (
+    CharSplitter,
+    Chunk,
+    HuggingfaceSplitter,
+    Language,
+    Splitter,
+    TiktokenSplitter,
+    WordSplitter,
+)
+
+TOKENIZER_PATH = Path(__file__).parents[3] / "tests" / "testdata" / "tokenizer.json"
+
+PYTHON_INPUT = b"""def add(a, b):
+    return a + b
+
+
+class Calculator:
+    def mul(self, a, b):
+        return a * b
+"""
+
+
+def count_vowels(text: str) -> int:
+    return sum(text.count(vowel) for vowel in "aeiou")
+
+
+@pytest.mark.parametrize(
+    "splitter",
+    [
+        CharSplitter(language=Language.Python, max_size=30),
+        WordSplitter(language=Language.Python, max_size=8),
+        TiktokenSplitter(language=Language.Python, max_size=10, model="gpt-4o"),
+        Splitter(language=Language.Python, max_size=5, sizer=count_vowels),
+        HuggingfaceSplitter.from_file(
+            language=Language.Python, max_size=4, path=TOKENIZER_PATH
+        ),
+        HuggingfaceSplitter.from_bytes(
+            language=Language.Python,
+            max_size=4,
+            data=TOKENIZER_PATH.read_bytes(),
+            add_special_tokens=True,
+        ),
+    ],
+)
+def test_pickle_splitter(splitter: object) -> None:
+    restored = pickle.loads(pickle.dumps(splitter))
+
+    assert type(restored) is type(splitter)
+    assert restored.split(PYTHON_INPUT) == splitter.split(PYTHON_INPUT)
+
+
+def test_pickle_tiktoken_model() -> None:
+    splitter = TiktokenSplitter(language=Language.Python, max_size=10, model="gpt-4o")
+    restored = pickle.loads(pickle.dumps(splitter))
+
+    # The model is resolved to its encoding when pickling
+    assert restored.split(PYTHON_INPUT)[0].tokenizer == "o200k_base"
+
+
+def test_pickle_unpicklable_sizer() -> None:
+    splitter = Splitter(language=Language.Python, max_size=5, sizer=lambda text: 1)
+    with pytest.raises((pickle.PicklingError, AttributeError)):
+        pickle.dumps(splitter)
+
+
+def test_pickle_capsule_language() -> None:
+    tree_sitter_python = pytest.importorskip("tree_sitter_python")
+    splitter = CharSplitter(language=tree_sitter_python.language(), max_size=30)
+
+    with pytest.raises(TypeError, match="`language` is a tree-sitter capsule"):
+        pickle.dumps(splitter)
+
+
+def test_pickle_language() -> None:
+    for language in [Language.Python, Language.CSharp, Language.Tsx]:
+        assert pickle.loads(pickle.dumps(language)) == language
+
+
+def test_pickle_chunk() -> None:
+    splitter = TiktokenSplitter(language=Language.Python, max_size=10)
+    chunks = splitter.split(PYTHON_INPUT)
+
+    restored = pickle.loads(pickle.dumps(chunks))
+    assert restored == chunks
+    assert all(isinstance(chunk, Chunk) for chunk in restored)
+    assert restored[0].tokenizer == "cl100k_base"
+
+    offsets = list(splitter.iter_split(PYTHON_INPUT, offsets_only=True))
+    assert pickle.loads(pickle.dumps(offsets)) == offsets
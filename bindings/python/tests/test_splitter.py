@@ -0,0 +1,63 @@
+import pytest
+from code_splitter import CharSplitter, Language, Splitter
+
+PYTHON_INPUT = b"""def add(a, b):
+    return a + b
+
+
+def sub(a, b):
+    return a - b
+
+
+class Calculator:
+    def mul(self, a, b):
+        return a * b
+"""
+
+
+class VowelCounter:
+    def size(self, text: str) -> int:
+        return sum(text.count(vowel) for vowel in "aeiou")
+
+
+def test_callable_sizer() -> None:
+    splitter = Splitter(language=Language.Python, max_size=50, sizer=len)
+    chunks = splitter.split(PYTHON_INPUT)
+
+    # `len` counts characters, so the chunks match the character splitter
+    expected = CharSplitter(language=Language.Python, max_size=50).split(PYTHON_INPUT)
+    assert [chunk.text for chunk in chunks] == [chunk.text for chunk in expected]
+    assert [chunk.size for chunk in chunks] == [chunk.size for chunk in expected]
+
+
+def test_object_sizer() -> None:
+    splitter = Splitter(language=Language.Python, max_size=5, sizer=VowelCounter())
+    chunks = splitter.split(PYTHON_INPUT)
+
+    for chunk in chunks:
+        assert chunk.size == VowelCounter().size(chunk.text)
+        assert chunk.size <= 5
+
+
+def test_sizer_exception_propagates() -> None:
+    def failing(text: str) -> int:
+        raise KeyError("quota exceeded")
+
+    splitter = Splitter(language=Language.Python, max_size=50, sizer=failing)
+    with pytest.raises(KeyError):
+        splitter.split(PYTHON_INPUT)
+
+
+def test_invalid_size() -> None:
+    splitter = Splitter(language=Language.Python, max_size=50, sizer=lambda _: -1)
+    with pytest.raises(OverflowError):
+        splitter.split(PYTHON_INPUT)
+
+    splitter = Splitter(language=Language.Python, max_size=50, sizer=lambda _: "1")
+    with pytest.raises(TypeError):
+        splitter.split(PYTHON_INPUT)
+
+
+def test_invalid_sizer() -> None:
+    with pytest.raises(TypeError):
+        Splitter(language=Language.Python, max_size=50, sizer=42)
@@ -0,0 +1,44 @@
+import ast
+import inspect
+from pathlib import Path
+
+import code_splitter
+
+STUBS_PATH = Path(__file__).parents[1] / "code_splitter.pyi"
+
+
+def stub_classes() -> dict[str, set[str]]:
+    module = ast.parse(STUBS_PATH.read_text())
+    classes = {}
+    for node in module.body:
+        if isinstance(node, ast.ClassDef) and not node.name.startswith("_"):
+            classes[node.name] = {
+                name
+                for item in node.body
+                if isinstance(item, (ast.FunctionDef, ast.AnnAssign))
+                for name in [
+                    item.target.id if isinstance(item, ast.AnnAssign) else item.name
+                ]
+                if not name.startswith("_")
+            }
+    return classes
+
+
+def test_stubs_cover_module() -> None:
+    public = {name for name in dir(code_splitter) if not name.startswith("_")}
+    classes = {
+        name for name in public if inspect.isclass(getattr(code_splitter, name))
+    }
+    assert classes == set(stub_classes())
+
+
+def test_stubs_cover_members() -> None:
+    for name, members in stub_classes().items():
+        cls = getattr(code_splitter, name)
+        public = {
+            member
+            for member in vars(cls)
+            if not member.startswith("_") and member not in vars(RuntimeError)
+        }
+        # Every stubbed member exists, and every public member is stubbed
+        assert members == public, name
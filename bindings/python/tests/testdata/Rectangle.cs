@@ -0,0 +1,31 @@
+using System;
+
+namespace Shapes
+{
+    /// <summary>A rectangle with a width and a height.</summary>
+    public class Rectangle
+    {
+        public double Width { get; }
+        public double Height { get; }
+
+        public Rectangle(double width, double height)
+        {
+            Width = width;
+            Height = height;
+        }
+
+        public double Area() => Width * Height;
+
+        public double Perimeter()
+        {
+            return 2 * (Width + Height);
+        }
+
+        public static void Main()
+        {
+            var rect = new Rectangle(3.0, 4.0);
+            Console.WriteLine($"area: {rect.Area()}");
+            Console.WriteLine($"perimeter: {rect.Perimeter()}");
+        }
+    }
+}
@@ -0,0 +1,26 @@
+package shapes;
+
+/** A rectangle with a width and a height. */
+public class Rectangle {
+    private final double width;
+    private final double height;
+
+    public Rectangle(double width, double height) {
+        this.width = width;
+        this.height = height;
+    }
+
+    public double area() {
+        return width * height;
+    }
+
+    public double perimeter() {
+        return 2 * (width + height);
+    }
+
+    public static void main(String[] args) {
+        Rectangle rect = new Rectangle(3.0, 4.0);
+        System.out.println("area: " + rect.area());
+        System.out.println("perimeter: " + rect.perimeter());
+    }
+}
@@ -0,0 +1,24 @@
+package shapes
+
+/** A rectangle with a width and a height. */
+data class Rectangle(val width: Double, val height: Double) {
+    fun area(): Double {
+        return width * height
+    }
+
+    fun perimeter(): Double = 2 * (width + height)
+
+    companion object {
+        fun square(side: Double) = Rectangle(side, side)
+    }
+}
+
+fun describe(rect: Rectangle): String {
+    return "area: ${rect.area()}, perimeter: ${rect.perimeter()}"
+}
+
+fun main() {
+    val rect = Rectangle(3.0, 4.0)
+    println(describe(rect))
+    println(describe(Rectangle.square(2.0)))
+}
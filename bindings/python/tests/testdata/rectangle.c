@@ -0,0 +1,22 @@
+#include <stdio.h>
+
+/* A rectangle with a width and a height. */
+typedef struct {
+    double width;
+    double height;
+} Rectangle;
+
+double area(const Rectangle *rect) {
+    return rect->width * rect->height;
+}
+
+double perimeter(const Rectangle *rect) {
+    return 2 * (rect->width + rect->height);
+}
+
+int main(void) {
+    Rectangle rect = {3.0, 4.0};
+    printf("area: %f\n", area(&rect));
+    printf("perimeter: %f\n", perimeter(&rect));
+    return 0;
+}
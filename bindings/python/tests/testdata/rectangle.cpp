@@ -0,0 +1,30 @@
+#include <iostream>
+
+namespace shapes {
+
+// A rectangle with a width and a height.
+class Rectangle {
+public:
+    Rectangle(double width, double height) : width_(width), height_(height) {}
+
+    double area() const {
+        return width_ * height_;
+    }
+
+    double perimeter() const {
+        return 2 * (width_ + height_);
+    }
+
+private:
+    double width_;
+    double height_;
+};
+
+}  // namespace shapes
+
+int main() {
+    shapes::Rectangle rect(3.0, 4.0);
+    std::cout << "area: " << rect.area() << std::endl;
+    std::cout << "perimeter: " << rect.perimeter() << std::endl;
+    return 0;
+}
@@ -0,0 +1,25 @@
+package main
+
+import "fmt"
+
+// Rectangle has a width and a height.
+type Rectangle struct {
+	Width  float64
+	Height float64
+}
+
+// Area returns the area of the rectangle.
+func (r Rectangle) Area() float64 {
+	return r.Width * r.Height
+}
+
+// Perimeter returns the perimeter of the rectangle.
+func (r Rectangle) Perimeter() float64 {
+	return 2 * (r.Width + r.Height)
+}
+
+func main() {
+	rect := Rectangle{Width: 3, Height: 4}
+	fmt.Println("area:", rect.Area())
+	fmt.Println("perimeter:", rect.Perimeter())
+}
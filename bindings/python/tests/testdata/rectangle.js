@@ -0,0 +1,24 @@
+/** A rectangle with a width and a height. */
+class Rectangle {
+  constructor(width, height) {
+    this.width = width;
+    this.height = height;
+  }
+
+  area() {
+    return this.width * this.height;
+  }
+
+  perimeter() {
+    return 2 * (this.width + this.height);
+  }
+}
+
+function describe(rect) {
+  return `area: ${rect.area()}, perimeter: ${rect.perimeter()}`;
+}
+
+const rect = new Rectangle(3, 4);
+console.log(describe(rect));
+
+module.exports = { Rectangle, describe };
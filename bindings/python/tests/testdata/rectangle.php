@@ -0,0 +1,29 @@
+<?php
+
+namespace Shapes;
+
+/**
+ * A rectangle with a width and a height.
+ */
+class Rectangle
+{
+    public function __construct(
+        private float $width,
+        private float $height,
+    ) {
+    }
+
+    public function area(): float
+    {
+        return $this->width * $this->height;
+    }
+
+    public function perimeter(): float
+    {
+        return 2 * ($this->width + $this->height);
+    }
+}
+
+$rect = new Rectangle(3.0, 4.0);
+echo "area: " . $rect->area() . "\n";
+echo "perimeter: " . $rect->perimeter() . "\n";
@@ -0,0 +1,24 @@
+from dataclasses import dataclass
+
+
+@dataclass
+class Rectangle:
+    """A rectangle with a width and a height."""
+
+    width: float
+    height: float
+
+    def area(self) -> float:
+        return self.width * self.height
+
+    def perimeter(self) -> float:
+        return 2 * (self.width + self.height)
+
+
+def describe(rect: Rectangle) -> str:
+    return f"area: {rect.area()}, perimeter: {rect.perimeter()}"
+
+
+if __name__ == "__main__":
+    rect = Rectangle(3.0, 4.0)
+    print(describe(rect))
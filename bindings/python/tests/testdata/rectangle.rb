@@ -0,0 +1,24 @@
+# A rectangle with a width and a height.
+class Rectangle
+  attr_reader :width, :height
+
+  def initialize(width, height)
+    @width = width
+    @height = height
+  end
+
+  def area
+    width * height
+  end
+
+  def perimeter
+    2 * (width + height)
+  end
+
+  def to_s
+    "area: #{area}, perimeter: #{perimeter}"
+  end
+end
+
+rect = Rectangle.new(3, 4)
+puts rect
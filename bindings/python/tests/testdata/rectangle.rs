@@ -0,0 +1,26 @@
+/// A rectangle with a width and a height.
+#[derive(Debug, Clone, Copy)]
+struct Rectangle {
+    width: f64,
+    height: f64,
+}
+
+impl Rectangle {
+    fn new(width: f64, height: f64) -> Self {
+        Self { width, height }
+    }
+
+    fn area(&self) -> f64 {
+        self.width * self.height
+    }
+
+    fn perimeter(&self) -> f64 {
+        2.0 * (self.width + self.height)
+    }
+}
+
+fn main() {
+    let rect = Rectangle::new(3.0, 4.0);
+    println!("area: {}", rect.area());
+    println!("perimeter: {}", rect.perimeter());
+}
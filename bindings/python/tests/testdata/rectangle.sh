@@ -0,0 +1,22 @@
+#!/usr/bin/env bash
+# Compute the area and perimeter of a rectangle.
+
+area() {
+    local width=$1
+    local height=$2
+    echo $((width * height))
+}
+
+perimeter() {
+    local width=$1
+    local height=$2
+    echo $((2 * (width + height)))
+}
+
+if [ "$#" -ne 2 ]; then
+    echo "usage: $0 WIDTH HEIGHT" >&2
+    exit 1
+fi
+
+echo "area: $(area "$1" "$2")"
+echo "perimeter: $(perimeter "$1" "$2")"
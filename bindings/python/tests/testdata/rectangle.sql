@@ -0,0 +1,23 @@
+-- Rectangles with a width and a height.
+CREATE TABLE rectangles (
+    id INTEGER PRIMARY KEY,
+    name VARCHAR(64) NOT NULL,
+    width REAL NOT NULL,
+    height REAL NOT NULL
+);
+
+INSERT INTO rectangles (id, name, width, height)
+VALUES
+    (1, 'small', 3, 4),
+    (2, 'square', 5, 5),
+    (3, 'wide', 10, 2);
+
+SELECT
+    name,
+    width * height AS area,
+    2 * (width + height) AS perimeter
+FROM rectangles
+WHERE width * height > 10
+ORDER BY area DESC;
+
+DELETE FROM rectangles WHERE width = height;
@@ -0,0 +1,24 @@
+import Foundation
+
+/// A rectangle with a width and a height.
+struct Rectangle {
+    let width: Double
+    let height: Double
+
+    func area() -> Double {
+        return width * height
+    }
+
+    func perimeter() -> Double {
+        return 2 * (width + height)
+    }
+}
+
+extension Rectangle: CustomStringConvertible {
+    var description: String {
+        return "area: \(area()), perimeter: \(perimeter())"
+    }
+}
+
+let rect = Rectangle(width: 3, height: 4)
+print(rect)
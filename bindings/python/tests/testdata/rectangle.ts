@@ -0,0 +1,27 @@
+/** Anything with an area. */
+interface Shape {
+  area(): number;
+  perimeter(): number;
+}
+
+/** A rectangle with a width and a height. */
+export class Rectangle implements Shape {
+  constructor(
+    private readonly width: number,
+    private readonly height: number,
+  ) {}
+
+  area(): number {
+    return this.width * this.height;
+  }
+
+  perimeter(): number {
+    return 2 * (this.width + this.height);
+  }
+}
+
+export function describe(shape: Shape): string {
+  return `area: ${shape.area()}, perimeter: ${shape.perimeter()}`;
+}
+
+console.log(describe(new Rectangle(3, 4)));
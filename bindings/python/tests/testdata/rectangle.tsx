@@ -0,0 +1,24 @@
+import React from "react";
+
+/** Props of the rectangle component. */
+interface RectangleProps {
+  width: number;
+  height: number;
+}
+
+/** Render a rectangle with its area and perimeter. */
+export function Rectangle({ width, height }: RectangleProps) {
+  const area = width * height;
+  const perimeter = 2 * (width + height);
+
+  return (
+    <div className="rectangle" style={{ width, height }}>
+      <p>Area: {area}</p>
+      <p>Perimeter: {perimeter}</p>
+    </div>
+  );
+}
+
+export default function App() {
+  return <Rectangle width={3} height={4} />;
+}
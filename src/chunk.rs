@@ -1,8 +1,12 @@
+use encoding_rs::Encoding;
 use std::fmt;
 use tree_sitter::Range;
 
 /// A chunk of code with a subtree and a range.
+///
+/// Chunks are only created by `Splitter`, and new fields may be added in minor releases.
 #[derive(Debug)]
+#[non_exhaustive]
 pub struct Chunk {
     /// Subtree representation of the code chunk.
     pub subtree: String,
@@ -10,6 +14,12 @@ pub struct Chunk {
     pub range: Range,
     /// Size of the code chunk.
     pub size: usize,
+    /// Sizes of the code chunk measured for each limit, starting with `size`.
+    pub sizes: Vec<usize>,
+    /// Encoding of the code the chunk was split from.
+    pub encoding: &'static Encoding,
+    /// Name of the tokenizer the chunk was sized with, e.g. `cl100k_base`, if the sizer has one.
+    pub tokenizer: Option<String>,
 }
 
 impl fmt::Display for Chunk {
@@ -27,6 +37,15 @@ impl fmt::Display for Chunk {
 }
 
 impl Chunk {
+    /// Decode the text of the chunk from the original code with its encoding.
+    pub fn text(&self, code: &[u8]) -> String {
+        let bytes = &code[self.range.start_byte..self.range.end_byte];
+        self.encoding
+            .decode_without_bom_handling(bytes)
+            .0
+            .into_owned()
+    }
+
     pub fn utf8_lossy(&self, code: &[u8]) -> String {
         String::from_utf8_lossy(&code[self.range.start_byte..self.range.end_byte]).to_string()
     }
@@ -0,0 +1,504 @@
+use std::collections::HashMap;
+use std::path::Path;
+use tree_sitter::Language;
+
+/// Number of lines at the start and end of a file searched for editor modelines.
+const MODELINE_SEARCH_LINES: usize = 5;
+
+/// Built-in mapping of file extensions to language identifiers.
+const EXTENSIONS: &[(&str, &str)] = &[
+    ("bash", "bash"),
+    ("c", "c"),
+    ("cc", "cpp"),
+    ("cpp", "cpp"),
+    ("cs", "csharp"),
+    ("css", "css"),
+    ("cxx", "cpp"),
+    ("go", "go"),
+    ("h", "c"),
+    ("hh", "cpp"),
+    ("hpp", "cpp"),
+    ("htm", "html"),
+    ("html", "html"),
+    ("java", "java"),
+    ("js", "javascript"),
+    ("json", "json"),
+    ("jsx", "javascript"),
+    ("kt", "kotlin"),
+    ("kts", "kotlin"),
+    ("markdown", "markdown"),
+    ("md", "markdown"),
+    ("mjs", "javascript"),
+    ("cjs", "javascript"),
+    ("php", "php"),
+    ("py", "python"),
+    ("pyi", "python"),
+    ("rb", "ruby"),
+    ("rs", "rust"),
+    ("sh", "bash"),
+    ("sql", "sql"),
+    ("swift", "swift"),
+    ("toml", "toml"),
+    ("ts", "typescript"),
+    ("tsx", "tsx"),
+    ("yaml", "yaml"),
+    ("yml", "yaml"),
+];
+
+/// Built-in mapping of special file names to language identifiers.
+const FILENAMES: &[(&str, &str)] = &[
+    (".bashrc", "bash"),
+    (".bash_profile", "bash"),
+    (".zshrc", "bash"),
+    ("Cargo.lock", "toml"),
+    ("Dockerfile", "dockerfile"),
+    ("Gemfile", "ruby"),
+    ("Makefile", "make"),
+    ("Pipfile", "toml"),
+    ("Rakefile", "ruby"),
+    ("go.mod", "gomod"),
+    ("go.sum", "gosum"),
+    ("makefile", "make"),
+];
+
+/// Built-in mapping of shebang interpreters to language identifiers.
+const INTERPRETERS: &[(&str, &str)] = &[
+    ("bash", "bash"),
+    ("deno", "typescript"),
+    ("node", "javascript"),
+    ("nodejs", "javascript"),
+    ("php", "php"),
+    ("python", "python"),
+    ("ruby", "ruby"),
+    ("sh", "bash"),
+    ("ts-node", "typescript"),
+    ("zsh", "bash"),
+];
+
+/// Built-in mapping of editor modeline names to language identifiers.
+///
+/// Language identifiers themselves are always accepted in modelines, so only names that differ
+/// from an identifier need to be listed here.
+const ALIASES: &[(&str, &str)] = &[
+    ("c++", "cpp"),
+    ("cs", "csharp"),
+    ("golang", "go"),
+    ("js", "javascript"),
+    ("md", "markdown"),
+    ("py", "python"),
+    ("rs", "rust"),
+    ("sh", "bash"),
+    ("shell-script", "bash"),
+    ("ts", "typescript"),
+    ("yml", "yaml"),
+];
+
+/// A registry for detecting the language of a file and looking up its tree-sitter grammar.
+///
+/// Languages are identified by lowercase names such as `"rust"` or `"python"`. The default
+/// registry knows common file extensions, special file names, shebang interpreters and editor
+/// modeline names. Every mapping can be extended or overridden, and grammars can be registered
+/// under any identifier.
+///
+/// ```
+/// use code_splitter::{CharCounter, LanguageRegistry, Splitter};
+/// use std::path::Path;
+///
+/// let mut registry = LanguageRegistry::default();
+/// registry.register_extension("mdx", "markdown");
+/// registry.register_grammar("markdown", tree_sitter_md::language());
+///
+/// let code = b"# hello, world!";
+/// let name = registry.detect(Path::new("README.mdx"), code).unwrap();
+/// assert_eq!(name, "markdown");
+///
+/// let lang = registry.grammar(name).unwrap();
+/// let splitter = Splitter::new(lang, CharCounter).unwrap();
+/// let chunks = splitter.split(code).unwrap();
+/// ```
+#[derive(Clone, Debug)]
+pub struct LanguageRegistry {
+    /// File extensions without the leading dot.
+    extensions: HashMap<String, String>,
+    /// Exact file names.
+    filenames: HashMap<String, String>,
+    /// Shebang interpreter names.
+    interpreters: HashMap<String, String>,
+    /// Editor modeline names.
+    aliases: HashMap<String, String>,
+    /// Tree-sitter grammars keyed by language identifier.
+    grammars: HashMap<String, Language>,
+}
+
+impl Default for LanguageRegistry {
+    /// Create a registry with the built-in mappings and the grammars of all enabled
+    /// [`Language`](crate::Language) features.
+    fn default() -> Self {
+        fn to_map(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+            pairs
+                .iter()
+                .map(|(key, name)| (key.to_string(), name.to_string()))
+                .collect()
+        }
+
+        Self {
+            extensions: to_map(EXTENSIONS),
+            filenames: to_map(FILENAMES),
+            interpreters: to_map(INTERPRETERS),
+            aliases: to_map(ALIASES),
+            grammars: crate::Language::ALL
+                .iter()
+                .map(|language| (language.name().to_string(), language.tree_sitter_language()))
+                .collect(),
+        }
+    }
+}
+
+impl LanguageRegistry {
+    /// Create an empty registry without any mappings or grammars.
+    pub fn new() -> Self {
+        Self {
+            extensions: HashMap::new(),
+            filenames: HashMap::new(),
+            interpreters: HashMap::new(),
+            aliases: HashMap::new(),
+            grammars: HashMap::new(),
+        }
+    }
+
+    /// Map a file extension (without the leading dot) to a language, replacing any existing mapping.
+    /// Extensions are matched case-insensitively.
+    pub fn register_extension(&mut self, extension: &str, name: &str) -> &mut Self {
+        self.extensions
+            .insert(extension.to_lowercase(), name.to_string());
+        self
+    }
+
+    /// Map an exact file name such as `Dockerfile` to a language, replacing any existing mapping.
+    pub fn register_filename(&mut self, filename: &str, name: &str) -> &mut Self {
+        self.filenames
+            .insert(filename.to_string(), name.to_string());
+        self
+    }
+
+    /// Map a shebang interpreter such as `python` to a language, replacing any existing mapping.
+    pub fn register_interpreter(&mut self, interpreter: &str, name: &str) -> &mut Self {
+        self.interpreters
+            .insert(interpreter.to_string(), name.to_string());
+        self
+    }
+
+    /// Map an editor modeline name such as `py` to a language, replacing any existing mapping.
+    /// Aliases are matched case-insensitively.
+    pub fn register_alias(&mut self, alias: &str, name: &str) -> &mut Self {
+        self.aliases.insert(alias.to_lowercase(), name.to_string());
+        self
+    }
+
+    /// Register the tree-sitter grammar of a language, replacing any existing grammar.
+    pub fn register_grammar(&mut self, name: &str, language: Language) -> &mut Self {
+        self.grammars.insert(name.to_string(), language);
+        self
+    }
+
+    /// Load a tree-sitter grammar from a shared library and register it for a language.
+    ///
+    /// # Safety
+    ///
+    /// See [`load_language`](crate::load_language).
+    #[cfg(feature = "dynamic-loading")]
+    pub unsafe fn register_library(
+        &mut self,
+        name: &str,
+        path: impl AsRef<Path>,
+    ) -> crate::Result<&mut Self> {
+        let language = crate::load_language(path, name)?;
+        Ok(self.register_grammar(name, language))
+    }
+
+    /// Load a tree-sitter grammar from a WebAssembly module and register it for a language.
+    /// See [`load_wasm_language`](crate::load_wasm_language).
+    #[cfg(feature = "wasm")]
+    pub fn register_wasm(&mut self, name: &str, bytes: &[u8]) -> crate::Result<&mut Self> {
+        let language = crate::load_wasm_language(name, bytes)?;
+        Ok(self.register_grammar(name, language))
+    }
+
+    /// Get the tree-sitter grammar registered for a language.
+    pub fn grammar(&self, name: &str) -> Option<Language> {
+        self.grammars.get(name).cloned()
+    }
+
+    /// Detect the language of a file from its path and content.
+    ///
+    /// An editor modeline takes precedence, followed by the file name, the file extension and
+    /// finally the shebang line.
+    pub fn detect(&self, path: &Path, code: &[u8]) -> Option<&str> {
+        self.detect_modeline(code)
+            .or_else(|| self.detect_path(path))
+            .or_else(|| self.detect_shebang(code))
+    }
+
+    /// Detect the language of a file from its name or extension.
+    pub fn detect_path(&self, path: &Path) -> Option<&str> {
+        let filename = path.file_name()?.to_str()?;
+        if let Some(name) = self.filenames.get(filename) {
+            return Some(name);
+        }
+
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        self.extensions.get(&extension).map(String::as_str)
+    }
+
+    /// Detect the language of a file from its shebang line, e.g. `#!/usr/bin/env python3`.
+    pub fn detect_shebang(&self, code: &[u8]) -> Option<&str> {
+        let line = first_line(code);
+        let mut args = line.strip_prefix("#!")?.split_whitespace();
+        let mut program = basename(args.next()?);
+        if program == "env" {
+            // Skip options such as `env -S`
+            program = args.find(|arg| !arg.starts_with('-'))?;
+        }
+
+        // Strip version suffixes such as `python3.12`
+        let interpreter = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+        self.interpreters
+            .get(interpreter)
+            .or_else(|| self.interpreters.get(program))
+            .map(String::as_str)
+    }
+
+    /// Detect the language of a file from a vim or emacs modeline near its start or end.
+    pub fn detect_modeline(&self, code: &[u8]) -> Option<&str> {
+        let (head, tail) = head_and_tail(code);
+        let (head, tail) = (String::from_utf8_lossy(head), String::from_utf8_lossy(tail));
+
+        head.lines()
+            .chain(tail.lines().rev())
+            .filter_map(|line| parse_vim_modeline(line).or_else(|| parse_emacs_modeline(line)))
+            .find_map(|mode| self.resolve(&mode))
+    }
+
+    /// Resolve a modeline name to a known language identifier.
+    fn resolve(&self, mode: &str) -> Option<&str> {
+        let mode = mode.to_lowercase();
+        if let Some(name) = self.aliases.get(&mode) {
+            return Some(name);
+        }
+
+        // Accept any identifier the registry already knows about
+        self.grammars
+            .get_key_value(&mode)
+            .map(|(name, _)| name.as_str())
+            .or_else(|| {
+                self.extensions
+                    .values()
+                    .chain(self.filenames.values())
+                    .chain(self.interpreters.values())
+                    .find(|name| **name == mode)
+                    .map(String::as_str)
+            })
+    }
+}
+
+/// Get the first line of the code, decoded lossily.
+fn first_line(code: &[u8]) -> String {
+    let end = code.iter().position(|&b| b == b'\n').unwrap_or(code.len());
+    String::from_utf8_lossy(&code[..end]).trim_end().to_string()
+}
+
+/// Split the first and the last lines searched for modelines off the code, so that only they
+/// are decoded. The last lines never overlap the first ones.
+fn head_and_tail(code: &[u8]) -> (&[u8], &[u8]) {
+    let newlines = |(i, &b): (usize, &u8)| (b == b'\n').then_some(i + 1);
+    let head_end = code
+        .iter()
+        .enumerate()
+        .filter_map(newlines)
+        .nth(MODELINE_SEARCH_LINES - 1)
+        .unwrap_or(code.len());
+
+    let rest = &code[head_end..];
+    // A final newline ends the last line instead of starting another one
+    let body = rest.strip_suffix(b"\n").unwrap_or(rest);
+    let tail_start = body
+        .iter()
+        .enumerate()
+        .rev()
+        .filter_map(newlines)
+        .nth(MODELINE_SEARCH_LINES - 1)
+        .unwrap_or(0);
+    (&code[..head_end], &rest[tail_start..])
+}
+
+/// Get the last component of a `/`-separated path.
+fn basename(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Parse a vim modeline such as `vim: set ft=python:` or `vi: filetype=rust`.
+fn parse_vim_modeline(line: &str) -> Option<String> {
+    // The marker starts the line or follows whitespace, so that e.g. `index:` isn't one
+    let start = ["vim:", "vi:", "ex:"]
+        .iter()
+        .flat_map(|marker| {
+            line.match_indices(marker)
+                .filter(|(i, _)| {
+                    line[..*i]
+                        .chars()
+                        .next_back()
+                        .is_none_or(char::is_whitespace)
+                })
+                .map(|(i, _)| i + marker.len())
+        })
+        .min()?;
+
+    line[start..]
+        .split(|c: char| c == ':' || c.is_whitespace())
+        .filter_map(|option| option.split_once('='))
+        .find(|(key, _)| *key == "ft" || *key == "filetype" || *key == "syntax" || *key == "syn")
+        .map(|(_, value)| value.to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Parse an emacs modeline such as `-*- mode: python -*-` or `-*- python -*-`.
+fn parse_emacs_modeline(line: &str) -> Option<String> {
+    let start = line.find("-*-")? + 3;
+    let end = line[start..].find("-*-")? + start;
+    let body = line[start..end].trim();
+
+    if !body.contains(':') {
+        return Some(body.to_string()).filter(|mode| !mode.is_empty());
+    }
+
+    body.split(';')
+        .filter_map(|option| option.split_once(':'))
+        .find(|(key, _)| key.trim().eq_ignore_ascii_case("mode"))
+        .map(|(_, value)| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_extension() {
+        let registry = LanguageRegistry::default();
+        assert_eq!(registry.detect_path(Path::new("src/lib.rs")), Some("rust"));
+        assert_eq!(registry.detect_path(Path::new("main.PY")), Some("python"));
+        assert_eq!(registry.detect_path(Path::new("notes.txt")), None);
+    }
+
+    #[test]
+    fn test_detect_filename() {
+        let registry = LanguageRegistry::default();
+        assert_eq!(
+            registry.detect_path(Path::new("docker/Dockerfile")),
+            Some("dockerfile")
+        );
+        assert_eq!(registry.detect_path(Path::new("go.mod")), Some("gomod"));
+    }
+
+    #[test]
+    fn test_detect_shebang() {
+        let registry = LanguageRegistry::default();
+        assert_eq!(
+            registry.detect_shebang(b"#!/usr/bin/env python3\nprint(1)"),
+            Some("python")
+        );
+        assert_eq!(
+            registry.detect_shebang(b"#!/bin/bash\necho 1"),
+            Some("bash")
+        );
+        assert_eq!(
+            registry.detect_shebang(b"#!/usr/bin/env -S node --harmony\n"),
+            Some("javascript")
+        );
+        assert_eq!(registry.detect_shebang(b"print(1)"), None);
+    }
+
+    #[test]
+    fn test_detect_modeline() {
+        let registry = LanguageRegistry::default();
+        assert_eq!(
+            registry.detect_modeline(b"# vim: set ft=py:\nx = 1\n"),
+            Some("python")
+        );
+        assert_eq!(
+            registry.detect_modeline(b"// -*- mode: rust; indent-tabs-mode: nil -*-\n"),
+            Some("rust")
+        );
+        assert_eq!(registry.detect_modeline(b";; -*- Go -*-\n"), Some("go"));
+        assert_eq!(registry.detect_modeline(b"vim: ft=unknown\n"), None);
+    }
+
+    #[test]
+    fn test_detect_modeline_marker() {
+        let registry = LanguageRegistry::default();
+        assert_eq!(registry.detect_modeline(b"x = 1 # avi: ft=ruby\n"), None);
+        assert_eq!(registry.detect_modeline(b"index: ft=ruby\n"), None);
+        assert_eq!(
+            registry.detect_modeline(b"index: 1 vi: ft=ruby\n"),
+            Some("ruby")
+        );
+        assert_eq!(registry.detect_modeline(b"\tex:ft=ruby\n"), Some("ruby"));
+    }
+
+    #[test]
+    fn test_detect_modeline_head_and_tail() {
+        let registry = LanguageRegistry::default();
+        // Modelines in the middle of the file are ignored
+        let mut code = b"x = 1\n".repeat(MODELINE_SEARCH_LINES);
+        code.extend(b"\xff\xfe # vim: ft=ruby\n");
+        code.extend(b"x = 1\n".repeat(MODELINE_SEARCH_LINES));
+        assert_eq!(registry.detect_modeline(&code), None);
+        code.extend(b"# vim: ft=python\n");
+        assert_eq!(registry.detect_modeline(&code), Some("python"));
+
+        let code = b"x = 1\n# vim: ft=python";
+        assert_eq!(registry.detect_modeline(code), Some("python"));
+        assert_eq!(head_and_tail(code), (&code[..], &b""[..]));
+    }
+
+    #[test]
+    fn test_detect_precedence() {
+        let registry = LanguageRegistry::default();
+        let code = b"#!/usr/bin/env python\n# vim: ft=ruby\n";
+        assert_eq!(registry.detect(Path::new("script.py"), code), Some("ruby"));
+        assert_eq!(
+            registry.detect(Path::new("script.py"), b"#!/bin/sh\n"),
+            Some("python")
+        );
+        assert_eq!(
+            registry.detect(Path::new("script"), b"#!/bin/sh\n"),
+            Some("bash")
+        );
+    }
+
+    #[test]
+    fn test_register_override() {
+        let mut registry = LanguageRegistry::default();
+        registry
+            .register_extension("h", "cpp")
+            .register_extension("dsl", "mydsl")
+            .register_alias("my-dsl", "mydsl");
+
+        assert_eq!(registry.detect_path(Path::new("vec.h")), Some("cpp"));
+        assert_eq!(registry.detect_path(Path::new("rules.dsl")), Some("mydsl"));
+        assert_eq!(
+            registry.detect_modeline(b"# vim: ft=my-dsl\n"),
+            Some("mydsl")
+        );
+    }
+
+    #[test]
+    fn test_register_grammar() {
+        let mut registry = LanguageRegistry::new();
+        assert!(registry.grammar("rust").is_none());
+
+        registry.register_grammar("rust", tree_sitter_rust::language());
+        assert!(registry.grammar("rust").is_some());
+        assert_eq!(registry.detect_modeline(b"// vim: ft=rust\n"), Some("rust"));
+    }
+}
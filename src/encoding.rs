@@ -0,0 +1,343 @@
+use encoding_rs::{DecoderResult, Encoding, UTF_8};
+use std::borrow::Cow;
+use std::str;
+use tree_sitter::Range;
+
+/// Number of leading bytes searched for an encoding declaration.
+const DECLARATION_SEARCH_BYTES: usize = 1024;
+
+/// Source code decoded to UTF-8 for parsing, with a map back to the original bytes.
+pub(crate) struct Source<'a> {
+    /// Encoding of the original bytes.
+    pub encoding: &'static Encoding,
+    /// UTF-8 text handed to tree-sitter and the sizer.
+    pub text: Cow<'a, [u8]>,
+    /// Original byte offset of every byte in `text`, plus one for its end. `None` if `text` is
+    /// the original bytes after the BOM.
+    offsets: Option<Vec<usize>>,
+    /// Length of the stripped BOM.
+    bom_len: usize,
+}
+
+impl<'a> Source<'a> {
+    /// Decode the code with the given encoding, or detect it from a BOM or a declaration. A
+    /// given encoding always wins, but a BOM is skipped even if it belongs to another encoding.
+    pub fn new(code: &'a [u8], encoding: Option<&'static Encoding>) -> Self {
+        let (encoding, bom_len) = match (encoding, Encoding::for_bom(code)) {
+            (Some(encoding), Some((_, bom_len))) => (encoding, bom_len),
+            (Some(encoding), None) => (encoding, 0),
+            (None, Some((bom, bom_len))) => (bom, bom_len),
+            // Declarations only matter for non-ASCII text, which is rarely valid UTF-8 otherwise
+            (None, None) if str::from_utf8(code).is_ok() => (UTF_8, 0),
+            (None, None) => (detect_declaration(code).unwrap_or(UTF_8), 0),
+        };
+
+        let body = &code[bom_len..];
+        if encoding == UTF_8 && str::from_utf8(body).is_ok() {
+            return Self {
+                encoding,
+                text: Cow::Borrowed(body),
+                offsets: None,
+                bom_len,
+            };
+        }
+
+        let (text, offsets) = transcode(body, encoding, bom_len);
+        Self {
+            encoding,
+            text: Cow::Owned(text.into_bytes()),
+            offsets: Some(offsets),
+            bom_len,
+        }
+    }
+
+    /// Map a byte offset in the decoded text to the original bytes.
+    fn original_offset(&self, offset: usize) -> usize {
+        match &self.offsets {
+            Some(offsets) => offsets[offset],
+            None => offset + self.bom_len,
+        }
+    }
+
+    /// Map a range in the decoded text to the original bytes. Columns become byte offsets from
+    /// the start of the line in the original bytes.
+    pub fn original_range(&self, range: Range) -> Range {
+        let mut original = range;
+        original.start_byte = self.original_offset(range.start_byte);
+        original.end_byte = self.original_offset(range.end_byte);
+
+        let start_line = self.original_offset(range.start_byte - range.start_point.column);
+        let end_line = self.original_offset(range.end_byte - range.end_point.column);
+        original.start_point.column = original.start_byte - start_line;
+        original.end_point.column = original.end_byte - end_line;
+        original
+    }
+}
+
+/// Decode the bytes to UTF-8, replacing malformed sequences, and record the original offset
+/// of every decoded byte.
+fn transcode(bytes: &[u8], encoding: &'static Encoding, base: usize) -> (String, Vec<usize>) {
+    let mut out = Transcoded {
+        text: String::with_capacity(bytes.len()),
+        offsets: Vec::with_capacity(bytes.len() + 1),
+    };
+
+    if encoding == UTF_8 {
+        // Well-formed runs keep their bytes, so they map to the original bytes one to one
+        let mut start = base;
+        for chunk in bytes.utf8_chunks() {
+            out.push_str(chunk.valid(), start);
+            start += chunk.valid().len();
+            if !chunk.invalid().is_empty() {
+                out.push(char::REPLACEMENT_CHARACTER, start);
+                start += chunk.invalid().len();
+            }
+        }
+    } else if encoding.is_single_byte() {
+        // Every byte decodes to exactly one character
+        let (text, _) = encoding.decode_without_bom_handling(bytes);
+        for (i, c) in text.chars().enumerate() {
+            out.push(c, base + i);
+        }
+    } else {
+        transcode_chars(bytes, encoding, base, &mut out);
+    }
+    out.offsets.push(base + bytes.len());
+
+    (out.text, out.offsets)
+}
+
+/// Decode the bytes one character per call, so that the decoder reports where each character
+/// starts. A character that needs a surrogate pair, or a sequence that decodes to two
+/// characters, is retried with room for two UTF-16 code units.
+fn transcode_chars(bytes: &[u8], encoding: &'static Encoding, base: usize, out: &mut Transcoded) {
+    let mut decoder = encoding.new_decoder_without_bom_handling();
+    let mut units = [0u16; 2];
+    let mut room = 1;
+    // Offset of the next byte to feed, and of the start of the next character
+    let mut pos = 0;
+    let mut char_start = 0;
+
+    loop {
+        let (result, read, written) =
+            decoder.decode_to_utf16_without_replacement(&bytes[pos..], &mut units[..room], true);
+        for c in char::decode_utf16(units[..written].iter().copied()) {
+            out.push(c.unwrap_or(char::REPLACEMENT_CHARACTER), base + char_start);
+        }
+        pos += read;
+
+        match result {
+            DecoderResult::InputEmpty => break,
+            DecoderResult::OutputFull if written == 0 => room = 2,
+            DecoderResult::OutputFull => {
+                room = 1;
+                char_start = pos;
+            }
+            DecoderResult::Malformed(malformed, extra) => {
+                // The extra bytes were read past the malformed sequence, and belong to the next
+                // character
+                let end = pos - usize::from(extra);
+                let start = end - usize::from(malformed);
+                out.push(char::REPLACEMENT_CHARACTER, base + start);
+                char_start = end;
+            }
+        }
+    }
+}
+
+/// Decoded text with the original offset of every byte of it.
+struct Transcoded {
+    text: String,
+    offsets: Vec<usize>,
+}
+
+impl Transcoded {
+    /// Append a character decoded from the original bytes starting at `offset`.
+    fn push(&mut self, c: char, offset: usize) {
+        self.text.push(c);
+        self.offsets.resize(self.text.len(), offset);
+    }
+
+    /// Append text copied unchanged from the original bytes starting at `offset`.
+    fn push_str(&mut self, text: &str, offset: usize) {
+        self.text.push_str(text);
+        self.offsets.extend(offset..offset + text.len());
+    }
+}
+
+/// Detect an encoding declared near the start of the code, such as a PEP 263 `coding:` comment,
+/// an XML declaration or an HTML `<meta charset>` tag.
+fn detect_declaration(code: &[u8]) -> Option<&'static Encoding> {
+    let head = &code[..code.len().min(DECLARATION_SEARCH_BYTES)];
+    let head = String::from_utf8_lossy(head).to_lowercase();
+
+    // PEP 263 and emacs declarations are comments on the first two lines
+    let coding = head
+        .lines()
+        .take(2)
+        .filter(|line| is_comment(line))
+        .find_map(|line| label_after(line, "coding:").or_else(|| label_after(line, "coding=")));
+    // Markup declarations only count inside their tags, not e.g. in `open(p, encoding="cp1251")`
+    let markup = tags(&head, "<?xml", "?>")
+        .find_map(|tag| label_after(tag, "encoding="))
+        .or_else(|| tags(&head, "<meta", ">").find_map(|tag| label_after(tag, "charset=")));
+
+    coding
+        .or(markup)
+        .and_then(|label| {
+            // Python spells some labels differently, e.g. `latin-1` for `latin1`
+            Encoding::for_label(label.as_bytes())
+                .or_else(|| Encoding::for_label(label.replace(['-', '_'], "").as_bytes()))
+        })
+        // A declaration readable as ASCII can't be UTF-16, so treat it like the HTML spec does
+        .map(Encoding::output_encoding)
+}
+
+/// Check whether the line is a comment, in the comment syntax of a common language.
+fn is_comment(line: &str) -> bool {
+    let line = line.trim_start();
+    ["#", "//", "/*", "--", ";", "%", "<!--"]
+        .iter()
+        .any(|start| line.starts_with(start))
+}
+
+/// Get the contents of every tag in the text that starts with `open`, up to its `close`.
+fn tags<'s>(text: &'s str, open: &'s str, close: &'s str) -> impl Iterator<Item = &'s str> {
+    text.match_indices(open).map(move |(i, _)| {
+        let tag = &text[i + open.len()..];
+        &tag[..tag.find(close).unwrap_or(tag.len())]
+    })
+}
+
+/// Get the encoding label that follows the marker, e.g. `latin-1` in `coding: latin-1`.
+fn label_after<'s>(text: &'s str, marker: &str) -> Option<&'s str> {
+    let start = text.find(marker)? + marker.len();
+    let label = text[start..]
+        .trim_start_matches(|c: char| c.is_whitespace() || c == '"' || c == '\'')
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.'))
+        .next()?;
+    Some(label).filter(|label| !label.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use encoding_rs::{SHIFT_JIS, UTF_16LE, WINDOWS_1252};
+
+    #[test]
+    fn test_valid_utf8_is_borrowed() {
+        let source = Source::new("café".as_bytes(), None);
+        assert_eq!(source.encoding, UTF_8);
+        assert!(matches!(source.text, Cow::Borrowed(_)));
+        assert_eq!(source.original_offset(5), 5);
+    }
+
+    #[test]
+    fn test_strip_utf8_bom() {
+        let source = Source::new(b"\xEF\xBB\xBFabc", None);
+        assert_eq!(source.encoding, UTF_8);
+        assert_eq!(&*source.text, b"abc");
+        assert_eq!(source.original_offset(0), 3);
+        assert_eq!(source.original_offset(3), 6);
+    }
+
+    #[test]
+    fn test_decode_utf16() {
+        let source = Source::new(b"\xFF\xFEa\x00\xE9\x00", None);
+        assert_eq!(source.encoding, UTF_16LE);
+        assert_eq!(&*source.text, "aé".as_bytes());
+        assert_eq!(source.original_offset(1), 4);
+        assert_eq!(source.original_offset(3), 6);
+    }
+
+    #[test]
+    fn test_invalid_utf8_is_lossy() {
+        let source = Source::new(b"a\xFFb", None);
+        assert_eq!(source.encoding, UTF_8);
+        assert_eq!(&*source.text, "a\u{FFFD}b".as_bytes());
+        assert_eq!(source.original_offset(4), 2);
+        assert_eq!(source.original_offset(5), 3);
+    }
+
+    #[test]
+    fn test_replacement_keeps_following_offsets() {
+        // The truncated `\xE9` and the newline after it are decoded together
+        let source = Source::new(b"caf\xE9\nx", None);
+        assert_eq!(&*source.text, "caf\u{FFFD}\nx".as_bytes());
+        assert_eq!(source.original_offset(3), 3);
+        assert_eq!(source.original_offset(6), 4);
+        assert_eq!(source.original_offset(7), 5);
+        assert_eq!(source.original_offset(8), 6);
+    }
+
+    #[test]
+    fn test_multibyte_malformed_offsets() {
+        // `\x82` starts a two-byte character, but a space can't end one
+        let source = Source::new(b"\x82\xA0\x82 \x82\xA2", Some(SHIFT_JIS));
+        let text = str::from_utf8(&source.text).unwrap();
+        assert_eq!(text, "あ\u{FFFD} い");
+        let offsets = text
+            .char_indices()
+            .map(|(i, _)| source.original_offset(i))
+            .collect::<Vec<_>>();
+        assert_eq!(offsets, [0, 2, 3, 4]);
+        assert_eq!(source.original_offset(text.len()), 6);
+    }
+
+    #[test]
+    fn test_utf16_surrogate_pair_offsets() {
+        let source = Source::new(b"\xFF\xFE\x3D\xD8\x00\xDEa\x00", None);
+        assert_eq!(&*source.text, "😀a".as_bytes());
+        assert_eq!(source.original_offset(4), 6);
+        assert_eq!(source.original_offset(5), 8);
+    }
+
+    #[test]
+    fn test_declared_encoding() {
+        let source = Source::new(b"# -*- coding: latin-1 -*-\nx = '\xE9'\n", None);
+        assert_eq!(source.encoding, WINDOWS_1252);
+        assert!(str::from_utf8(&source.text).unwrap().contains('é'));
+
+        let source = Source::new(b"<meta charset=\"shift_jis\">\x82\xA0", None);
+        assert_eq!(source.encoding, SHIFT_JIS);
+        assert!(str::from_utf8(&source.text).unwrap().ends_with('あ'));
+    }
+
+    #[test]
+    fn test_declaration_outside_markup_ignored() {
+        let code = b"with open(p, encoding=\"cp1251\") as f:\n    x = '\xE9'\n";
+        let source = Source::new(code, None);
+        assert_eq!(source.encoding, UTF_8);
+
+        let source = Source::new(
+            b"<?xml version=\"1.0\" encoding=\"latin1\"?><a>\xE9</a>",
+            None,
+        );
+        assert_eq!(source.encoding, WINDOWS_1252);
+        let source = Source::new(b"<a charset=\"latin1\">\xE9</a>", None);
+        assert_eq!(source.encoding, UTF_8);
+    }
+
+    #[test]
+    fn test_declaration_ignored_for_utf8() {
+        let source = Source::new("# coding: latin-1\nx = 'é'\n".as_bytes(), None);
+        assert_eq!(source.encoding, UTF_8);
+    }
+
+    #[test]
+    fn test_explicit_encoding_skips_other_bom() {
+        let source = Source::new(b"\xEF\xBB\xBFx\0", Some(UTF_16LE));
+        assert_eq!(source.encoding, UTF_16LE);
+        assert_eq!(&*source.text, b"x");
+        assert_eq!(source.original_offset(0), 3);
+        assert_eq!(source.original_offset(1), 5);
+    }
+
+    #[test]
+    fn test_explicit_encoding() {
+        let source = Source::new(b"\x82\xA0\x82\xA2", Some(SHIFT_JIS));
+        assert_eq!(&*source.text, "あい".as_bytes());
+        assert_eq!(source.original_offset(3), 2);
+        assert_eq!(source.original_offset(6), 4);
+    }
+}
@@ -1,2 +1,95 @@
-pub type Error = Box<dyn std::error::Error + Send + Sync>;
+use std::error;
+use std::fmt;
+use std::str::Utf8Error;
+use tree_sitter::LanguageError;
+
+/// Boxed error returned by a sizer or a grammar loader.
+pub type BoxError = Box<dyn error::Error + Send + Sync>;
+
+/// An error that occurred while loading a language or splitting code.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The tree-sitter grammar is incompatible with the tree-sitter library.
+    Language(LanguageError),
+    /// No bundled grammar is enabled for the language identifier.
+    UnsupportedLanguage(String),
+    /// A grammar could not be loaded from a shared library or a WebAssembly module.
+    LoadGrammar {
+        /// Name of the grammar.
+        name: String,
+        /// Reason the grammar could not be loaded.
+        source: BoxError,
+    },
+    /// Tree-sitter failed to parse the code.
+    Parse,
+    /// Text passed to the sizer is not valid UTF-8.
+    Utf8(Utf8Error),
+    /// The sizer failed to measure the size of a text.
+    Sizer(BoxError),
+    /// An argument is invalid, e.g. the name of an unknown tiktoken encoding.
+    InvalidArgument(String),
+    /// The WebAssembly runtime for wasm grammars could not be created.
+    #[cfg(feature = "wasm")]
+    Wasm(tree_sitter::WasmError),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Language(e) => write!(f, "Error loading tree-sitter language: {e}"),
+            Error::UnsupportedLanguage(name) => write!(f, "Unsupported language: {name}"),
+            Error::LoadGrammar { name, source } => {
+                write!(f, "Error loading grammar {name}: {source}")
+            }
+            Error::Parse => write!(f, "Error parsing code"),
+            Error::Utf8(e) => write!(f, "Invalid UTF-8 text: {e}"),
+            Error::Sizer(e) => write!(f, "Error sizing text: {e}"),
+            Error::InvalidArgument(message) => write!(f, "Invalid argument: {message}"),
+            #[cfg(feature = "wasm")]
+            Error::Wasm(e) => write!(f, "Error creating wasm store: {e}"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Language(e) => Some(e),
+            Error::LoadGrammar { source, .. } => Some(source.as_ref()),
+            Error::Utf8(e) => Some(e),
+            Error::Sizer(e) => Some(e.as_ref()),
+            #[cfg(feature = "wasm")]
+            Error::Wasm(e) => Some(e),
+            Error::UnsupportedLanguage(_) | Error::Parse | Error::InvalidArgument(_) => None,
+        }
+    }
+}
+
+impl From<LanguageError> for Error {
+    fn from(e: LanguageError) -> Self {
+        Error::Language(e)
+    }
+}
+
+impl From<Utf8Error> for Error {
+    fn from(e: Utf8Error) -> Self {
+        Error::Utf8(e)
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+
+    #[test]
+    fn test_source() {
+        let err = Error::Sizer("out of tokens".into());
+        assert_eq!(err.to_string(), "Error sizing text: out of tokens");
+        assert_eq!(err.source().unwrap().to_string(), "out of tokens");
+        assert!(Error::Parse.source().is_none());
+    }
+}
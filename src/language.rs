@@ -0,0 +1,280 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::Error;
+
+/// A programming language with a bundled tree-sitter grammar.
+///
+/// Each grammar is compiled in only when its cargo feature is enabled, e.g. `lang-rust` for
+/// [`Language::Rust`]. Grammars for other languages can still be used through
+/// [`Splitter::new`](crate::Splitter::new).
+///
+/// ```
+/// # #[cfg(feature = "lang-rust")]
+/// # {
+/// use code_splitter::{CharCounter, Language, Splitter};
+///
+/// let lang: Language = "rust".parse().unwrap();
+/// let splitter = Splitter::for_language(lang, CharCounter).unwrap();
+/// let chunks = splitter.split(b"fn main() {}").unwrap();
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Language {
+    #[cfg(feature = "lang-bash")]
+    Bash,
+    #[cfg(feature = "lang-c")]
+    C,
+    #[cfg(feature = "lang-cpp")]
+    Cpp,
+    #[cfg(feature = "lang-csharp")]
+    CSharp,
+    #[cfg(feature = "lang-css")]
+    Css,
+    #[cfg(feature = "lang-go")]
+    Golang,
+    #[cfg(feature = "lang-html")]
+    Html,
+    #[cfg(feature = "lang-java")]
+    Java,
+    #[cfg(feature = "lang-javascript")]
+    JavaScript,
+    #[cfg(feature = "lang-json")]
+    Json,
+    #[cfg(feature = "lang-kotlin")]
+    Kotlin,
+    #[cfg(feature = "lang-md")]
+    Markdown,
+    #[cfg(feature = "lang-php")]
+    Php,
+    #[cfg(feature = "lang-python")]
+    Python,
+    #[cfg(feature = "lang-ruby")]
+    Ruby,
+    #[cfg(feature = "lang-rust")]
+    Rust,
+    #[cfg(feature = "lang-sql")]
+    Sql,
+    #[cfg(feature = "lang-swift")]
+    Swift,
+    #[cfg(feature = "lang-toml")]
+    Toml,
+    #[cfg(feature = "lang-typescript")]
+    TypeScript,
+    #[cfg(feature = "lang-typescript")]
+    Tsx,
+    #[cfg(feature = "lang-yaml")]
+    Yaml,
+}
+
+impl Language {
+    /// All languages whose grammars are enabled.
+    pub const ALL: &'static [Language] = &[
+        #[cfg(feature = "lang-bash")]
+        Language::Bash,
+        #[cfg(feature = "lang-c")]
+        Language::C,
+        #[cfg(feature = "lang-cpp")]
+        Language::Cpp,
+        #[cfg(feature = "lang-csharp")]
+        Language::CSharp,
+        #[cfg(feature = "lang-css")]
+        Language::Css,
+        #[cfg(feature = "lang-go")]
+        Language::Golang,
+        #[cfg(feature = "lang-html")]
+        Language::Html,
+        #[cfg(feature = "lang-java")]
+        Language::Java,
+        #[cfg(feature = "lang-javascript")]
+        Language::JavaScript,
+        #[cfg(feature = "lang-json")]
+        Language::Json,
+        #[cfg(feature = "lang-kotlin")]
+        Language::Kotlin,
+        #[cfg(feature = "lang-md")]
+        Language::Markdown,
+        #[cfg(feature = "lang-php")]
+        Language::Php,
+        #[cfg(feature = "lang-python")]
+        Language::Python,
+        #[cfg(feature = "lang-ruby")]
+        Language::Ruby,
+        #[cfg(feature = "lang-rust")]
+        Language::Rust,
+        #[cfg(feature = "lang-sql")]
+        Language::Sql,
+        #[cfg(feature = "lang-swift")]
+        Language::Swift,
+        #[cfg(feature = "lang-toml")]
+        Language::Toml,
+        #[cfg(feature = "lang-typescript")]
+        Language::TypeScript,
+        #[cfg(feature = "lang-typescript")]
+        Language::Tsx,
+        #[cfg(feature = "lang-yaml")]
+        Language::Yaml,
+    ];
+
+    /// The identifier of the language, as used by [`LanguageRegistry`](crate::LanguageRegistry).
+    pub fn name(&self) -> &'static str {
+        match *self {
+            #[cfg(feature = "lang-bash")]
+            Language::Bash => "bash",
+            #[cfg(feature = "lang-c")]
+            Language::C => "c",
+            #[cfg(feature = "lang-cpp")]
+            Language::Cpp => "cpp",
+            #[cfg(feature = "lang-csharp")]
+            Language::CSharp => "csharp",
+            #[cfg(feature = "lang-css")]
+            Language::Css => "css",
+            #[cfg(feature = "lang-go")]
+            Language::Golang => "go",
+            #[cfg(feature = "lang-html")]
+            Language::Html => "html",
+            #[cfg(feature = "lang-java")]
+            Language::Java => "java",
+            #[cfg(feature = "lang-javascript")]
+            Language::JavaScript => "javascript",
+            #[cfg(feature = "lang-json")]
+            Language::Json => "json",
+            #[cfg(feature = "lang-kotlin")]
+            Language::Kotlin => "kotlin",
+            #[cfg(feature = "lang-md")]
+            Language::Markdown => "markdown",
+            #[cfg(feature = "lang-php")]
+            Language::Php => "php",
+            #[cfg(feature = "lang-python")]
+            Language::Python => "python",
+            #[cfg(feature = "lang-ruby")]
+            Language::Ruby => "ruby",
+            #[cfg(feature = "lang-rust")]
+            Language::Rust => "rust",
+            #[cfg(feature = "lang-sql")]
+            Language::Sql => "sql",
+            #[cfg(feature = "lang-swift")]
+            Language::Swift => "swift",
+            #[cfg(feature = "lang-toml")]
+            Language::Toml => "toml",
+            #[cfg(feature = "lang-typescript")]
+            Language::TypeScript => "typescript",
+            #[cfg(feature = "lang-typescript")]
+            Language::Tsx => "tsx",
+            #[cfg(feature = "lang-yaml")]
+            Language::Yaml => "yaml",
+        }
+    }
+
+    /// The bundled tree-sitter grammar of the language.
+    pub fn tree_sitter_language(&self) -> tree_sitter::Language {
+        match *self {
+            #[cfg(feature = "lang-bash")]
+            Language::Bash => tree_sitter_bash::language(),
+            #[cfg(feature = "lang-c")]
+            Language::C => tree_sitter_c::language(),
+            #[cfg(feature = "lang-cpp")]
+            Language::Cpp => tree_sitter_cpp::language(),
+            #[cfg(feature = "lang-csharp")]
+            Language::CSharp => tree_sitter_c_sharp::language(),
+            #[cfg(feature = "lang-css")]
+            Language::Css => tree_sitter_css::language(),
+            #[cfg(feature = "lang-go")]
+            Language::Golang => tree_sitter_go::language(),
+            #[cfg(feature = "lang-html")]
+            Language::Html => tree_sitter_html::language(),
+            #[cfg(feature = "lang-java")]
+            Language::Java => tree_sitter_java::language(),
+            #[cfg(feature = "lang-javascript")]
+            Language::JavaScript => tree_sitter_javascript::language(),
+            #[cfg(feature = "lang-json")]
+            Language::Json => tree_sitter_json::language(),
+            #[cfg(feature = "lang-kotlin")]
+            Language::Kotlin => tree_sitter_kotlin::language(),
+            #[cfg(feature = "lang-md")]
+            Language::Markdown => tree_sitter_md::language(),
+            #[cfg(feature = "lang-php")]
+            Language::Php => tree_sitter_php::language_php(),
+            #[cfg(feature = "lang-python")]
+            Language::Python => tree_sitter_python::language(),
+            #[cfg(feature = "lang-ruby")]
+            Language::Ruby => tree_sitter_ruby::language(),
+            #[cfg(feature = "lang-rust")]
+            Language::Rust => tree_sitter_rust::language(),
+            #[cfg(feature = "lang-sql")]
+            Language::Sql => tree_sitter_sequel::language(),
+            #[cfg(feature = "lang-swift")]
+            Language::Swift => tree_sitter_swift::language(),
+            #[cfg(feature = "lang-toml")]
+            Language::Toml => tree_sitter_toml_ng::language(),
+            #[cfg(feature = "lang-typescript")]
+            Language::TypeScript => tree_sitter_typescript::language_typescript(),
+            #[cfg(feature = "lang-typescript")]
+            Language::Tsx => tree_sitter_typescript::language_tsx(),
+            #[cfg(feature = "lang-yaml")]
+            Language::Yaml => tree_sitter_yaml::language(),
+        }
+    }
+}
+
+impl From<Language> for tree_sitter::Language {
+    fn from(language: Language) -> Self {
+        language.tree_sitter_language()
+    }
+}
+
+impl FromStr for Language {
+    type Err = Error;
+
+    /// Parse a language from its identifier, e.g. `"rust"`.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Language::ALL
+            .iter()
+            .find(|language| language.name() == name)
+            .copied()
+            .ok_or_else(|| Error::UnsupportedLanguage(name.to_string()))
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_str_unsupported() {
+        assert!("klingon".parse::<Language>().is_err());
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_from_str() {
+        assert_eq!("rust".parse::<Language>().unwrap(), Language::Rust);
+        assert_eq!(Language::Rust.to_string(), "rust");
+    }
+
+    #[cfg(all(feature = "lang-rust", feature = "lang-md"))]
+    #[test]
+    fn test_tree_sitter_language() {
+        let mut parser = tree_sitter::Parser::new();
+        for language in [Language::Rust, Language::Markdown] {
+            assert!(parser.set_language(&language.into()).is_ok());
+        }
+    }
+
+    #[cfg(feature = "lang-rust")]
+    #[test]
+    fn test_all_languages() {
+        let mut parser = tree_sitter::Parser::new();
+        for &language in Language::ALL {
+            assert!(parser.set_language(&language.into()).is_ok(), "{language}");
+            assert_eq!(language.name().parse::<Language>().unwrap(), language);
+        }
+    }
+}
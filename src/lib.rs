@@ -1,9 +1,31 @@
 mod chunk;
+mod detect;
+mod encoding;
 mod error;
+mod language;
+#[cfg(feature = "dynamic-loading")]
+mod loader;
 mod sizer;
 mod splitter;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod weights;
 
 pub use chunk::Chunk;
-pub use error::{Error, Result};
-pub use sizer::{CharCounter, Sizer, WordCounter};
+pub use detect::LanguageRegistry;
+pub use encoding_rs::Encoding;
+pub use error::{BoxError, Error, Result};
+pub use language::Language;
+#[cfg(feature = "dynamic-loading")]
+pub use loader::load_language;
+#[cfg(feature = "tokenizers")]
+pub use sizer::HuggingfaceCounter;
+#[cfg(feature = "tiktoken-rs")]
+pub use sizer::TiktokenCounter;
+pub use sizer::{
+    ByteCounter, CacheStats, CachedSizer, CharCounter, CodeTokenCounter, DisplayWidthCounter,
+    FnSizer, GraphemeCounter, LineCounter, NormalizedSizer, Sizer, WordCounter,
+};
 pub use splitter::Splitter;
+#[cfg(feature = "wasm")]
+pub use wasm::load_wasm_language;
@@ -0,0 +1,130 @@
+use libloading::{Library, Symbol};
+use std::path::Path;
+use tree_sitter::{ffi::TSLanguage, Language, LANGUAGE_VERSION, MIN_COMPATIBLE_LANGUAGE_VERSION};
+
+use crate::error::{BoxError, Error, Result};
+
+/// Load a tree-sitter grammar from a compiled shared library, e.g. `libtree-sitter-mydsl.so`.
+///
+/// The library must export a `tree_sitter_<name>` function returning the grammar, where dashes
+/// in `name` are replaced by underscores. The library stays loaded for the rest of the process,
+/// since the returned `Language` points into it.
+///
+/// # Safety
+///
+/// Loading a shared library runs its initialization code, and the exported function is trusted
+/// to return a valid grammar. Only load libraries from trusted sources.
+///
+/// ```no_run
+/// use code_splitter::{load_language, CharCounter, Splitter};
+///
+/// let lang = unsafe { load_language("path/to/libtree-sitter-mydsl.so", "mydsl") }.unwrap();
+/// let splitter = Splitter::new(lang, CharCounter).unwrap();
+/// ```
+pub unsafe fn load_language(path: impl AsRef<Path>, name: &str) -> Result<Language> {
+    let path = path.as_ref();
+    let load_error = |source: BoxError| Error::LoadGrammar {
+        name: name.to_string(),
+        source,
+    };
+
+    let library = Library::new(path)
+        .map_err(|e| load_error(format!("cannot open {}: {e}", path.display()).into()))?;
+
+    let symbol = format!("tree_sitter_{}", name.replace('-', "_"));
+    let language = {
+        let constructor: Symbol<unsafe extern "C" fn() -> *const TSLanguage> =
+            library.get(symbol.as_bytes()).map_err(|e| {
+                load_error(format!("cannot find {symbol} in {}: {e}", path.display()).into())
+            })?;
+        let raw = constructor();
+        if raw.is_null() {
+            return Err(load_error(
+                format!("{symbol} returned a null grammar").into(),
+            ));
+        }
+        Language::from_raw(raw)
+    };
+
+    let version = language.version();
+    if !(MIN_COMPATIBLE_LANGUAGE_VERSION..=LANGUAGE_VERSION).contains(&version) {
+        return Err(load_error(
+            format!(
+                "incompatible ABI version {version}, expected {MIN_COMPATIBLE_LANGUAGE_VERSION}..={LANGUAGE_VERSION}"
+            )
+            .into(),
+        ));
+    }
+
+    // Keep the library loaded, since the grammar's tables live in it
+    std::mem::forget(library);
+
+    Ok(language)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_missing_library() {
+        let err = unsafe { load_language("does/not/exist.so", "mydsl") }.unwrap_err();
+        assert!(matches!(err, Error::LoadGrammar { ref name, .. } if name == "mydsl"));
+        assert!(err.to_string().contains("does/not/exist.so"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_load_missing_symbol() {
+        let err = unsafe { load_language("libc.so.6", "my-dsl") }.unwrap_err();
+        assert!(err.to_string().contains("tree_sitter_my_dsl"));
+    }
+
+    /// Grammars that only carry an ABI version. Tree-sitter reads the version from the first
+    /// field of a grammar, and the zeroed fields after it are enough to drop the `Language`.
+    #[cfg(target_os = "linux")]
+    const FAKE_GRAMMARS: &str = r#"
+        struct fake_language { unsigned int version; void *fields[64]; };
+        static struct fake_language current = { CURRENT_VERSION };
+        static struct fake_language old = { 1 };
+        const void *tree_sitter_fake_current(void) { return &current; }
+        const void *tree_sitter_fake_old(void) { return &old; }
+    "#;
+
+    /// Compile the fake grammars into a shared library with the system C compiler.
+    #[cfg(target_os = "linux")]
+    fn build_fake_grammars() -> std::path::PathBuf {
+        use std::process::Command;
+
+        let dir = std::env::temp_dir().join(format!("code-splitter-loader-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let source = dir.join("fake.c");
+        let library = dir.join("libtree-sitter-fake.so");
+        std::fs::write(&source, FAKE_GRAMMARS).unwrap();
+
+        let status = Command::new("cc")
+            .args(["-shared", "-fPIC", "-o"])
+            .arg(&library)
+            .arg(format!("-DCURRENT_VERSION={LANGUAGE_VERSION}"))
+            .arg(&source)
+            .status()
+            .expect("a C compiler is needed to build the test grammars");
+        assert!(status.success());
+        library
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_load_grammar() {
+        let library = build_fake_grammars();
+
+        let language = unsafe { load_language(&library, "fake-current") }.unwrap();
+        assert_eq!(language.version(), LANGUAGE_VERSION);
+
+        let err = unsafe { load_language(&library, "fake-old") }.unwrap_err();
+        assert!(matches!(err, Error::LoadGrammar { ref name, .. } if name == "fake-old"));
+        assert!(err.to_string().contains("incompatible ABI version 1"));
+
+        std::fs::remove_dir_all(library.parent().unwrap()).unwrap();
+    }
+}
@@ -1,18 +1,213 @@
+mod bytes;
+pub use bytes::ByteCounter;
+
+mod cached;
+pub use cached::{CacheStats, CachedSizer};
+
 mod chars;
 pub use chars::CharCounter;
 
+mod closure;
+pub use closure::FnSizer;
+
+mod code_tokens;
+pub use code_tokens::CodeTokenCounter;
+
+mod graphemes;
+pub use graphemes::GraphemeCounter;
+
 #[cfg(feature = "tokenizers")]
 mod huggingface;
+#[cfg(feature = "tokenizers")]
+pub use huggingface::HuggingfaceCounter;
+
+mod lines;
+pub use lines::LineCounter;
+
+mod normalized;
+pub use normalized::NormalizedSizer;
 
 #[cfg(feature = "tiktoken-rs")]
 mod tiktoken;
+#[cfg(feature = "tiktoken-rs")]
+pub use tiktoken::TiktokenCounter;
+
+mod width;
+pub use width::DisplayWidthCounter;
 
 mod words;
 pub use words::WordCounter;
 
 use crate::error::Result;
+use std::sync::Arc;
 
 /// An interface for counting the size of a code chunk.
+///
+/// Besides concrete sizers, references and smart pointers to sizers are sizers too. This lets a
+/// single `Splitter<Box<dyn Sizer + Send + Sync>>` type hold a sizer chosen at runtime.
+///
+/// ```
+/// use code_splitter::{CharCounter, Sizer, Splitter, WordCounter};
+///
+/// let by_words = true;
+/// let sizer: Box<dyn Sizer + Send + Sync> = if by_words {
+///     Box::new(WordCounter)
+/// } else {
+///     Box::new(CharCounter)
+/// };
+///
+/// let lang = tree_sitter_md::language();
+/// let splitter = Splitter::new(lang, sizer).unwrap();
+/// let chunks = splitter.split(b"hello, world!").unwrap();
+/// assert_eq!(chunks[0].size, 2);
+/// ```
 pub trait Sizer {
     fn size(&self, text: &str) -> Result<usize>;
+
+    /// Count the sizes of several texts at once, in the same order as the texts.
+    ///
+    /// The default implementation sizes the texts one by one. Sizers that can measure many
+    /// texts faster together, such as tokenizers, override it: HuggingFace tokenizers encode
+    /// the batch at once, and tiktoken encodes large batches on several threads. `Splitter`
+    /// sizes all children of a node with a single call.
+    fn size_batch(&self, texts: &[&str]) -> Result<Vec<usize>> {
+        texts.iter().map(|text| self.size(text)).collect()
+    }
+
+    /// Get cheap bounds on the size of the text, like [`Iterator::size_hint`].
+    ///
+    /// The size returned by `size` must lie within the bounds, where `None` means there is no
+    /// known upper bound. `Splitter` skips exact sizing whenever the bounds alone show that a
+    /// text fits within `max_size` or exceeds it, so expensive sizers such as tokenizers only
+    /// measure the texts in between. The default implementation returns `(0, None)`.
+    fn size_hint(&self, text: &str) -> (usize, Option<usize>) {
+        let _ = text;
+        (0, None)
+    }
+
+    /// Name of the tokenizer or encoding the sizer counts tokens with, e.g. `cl100k_base`, if
+    /// it has one. `Splitter` records it in [`Chunk::tokenizer`](crate::Chunk::tokenizer), so
+    /// that chunks sized with different tokenizers can be told apart. Defaults to `None`.
+    fn tokenizer(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl<S> Sizer for &S
+where
+    S: Sizer + ?Sized,
+{
+    fn size(&self, text: &str) -> Result<usize> {
+        (**self).size(text)
+    }
+
+    fn size_batch(&self, texts: &[&str]) -> Result<Vec<usize>> {
+        (**self).size_batch(texts)
+    }
+
+    fn size_hint(&self, text: &str) -> (usize, Option<usize>) {
+        (**self).size_hint(text)
+    }
+
+    fn tokenizer(&self) -> Option<&str> {
+        (**self).tokenizer()
+    }
+}
+
+impl<S> Sizer for Box<S>
+where
+    S: Sizer + ?Sized,
+{
+    fn size(&self, text: &str) -> Result<usize> {
+        (**self).size(text)
+    }
+
+    fn size_batch(&self, texts: &[&str]) -> Result<Vec<usize>> {
+        (**self).size_batch(texts)
+    }
+
+    fn size_hint(&self, text: &str) -> (usize, Option<usize>) {
+        (**self).size_hint(text)
+    }
+
+    fn tokenizer(&self) -> Option<&str> {
+        (**self).tokenizer()
+    }
+}
+
+impl<S> Sizer for Arc<S>
+where
+    S: Sizer + ?Sized,
+{
+    fn size(&self, text: &str) -> Result<usize> {
+        (**self).size(text)
+    }
+
+    fn size_batch(&self, texts: &[&str]) -> Result<Vec<usize>> {
+        (**self).size_batch(texts)
+    }
+
+    fn size_hint(&self, text: &str) -> (usize, Option<usize>) {
+        (**self).size_hint(text)
+    }
+
+    fn tokenizer(&self) -> Option<&str> {
+        (**self).tokenizer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dyn_sizers() {
+        let sizers: Vec<Box<dyn Sizer + Send + Sync>> = vec![
+            Box::new(CharCounter),
+            Box::new(&WordCounter),
+            Box::new(Arc::new(LineCounter) as Arc<dyn Sizer + Send + Sync>),
+            Box::new(FnSizer::new(|text: &str| Ok(text.len()))),
+        ];
+        let sizes = sizers
+            .iter()
+            .map(|sizer| sizer.size("hello, world!").unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, vec![13, 2, 1, 13]);
+    }
+
+    #[test]
+    fn test_size_hints() {
+        let sizers: Vec<Box<dyn Sizer>> = vec![
+            Box::new(ByteCounter),
+            Box::new(CharCounter),
+            Box::new(CodeTokenCounter),
+            Box::new(DisplayWidthCounter),
+            Box::new(GraphemeCounter),
+            Box::new(LineCounter),
+            Box::new(WordCounter),
+        ];
+        let texts = [
+            "",
+            "a",
+            "café  au lait",
+            "\n\n",
+            "  x\ny \n",
+            "日本語のテキスト",
+        ];
+        for sizer in &sizers {
+            for text in texts {
+                let size = sizer.size(text).unwrap();
+                let (lower, upper) = sizer.size_hint(text);
+                assert!(lower <= size, "{size} is below the lower bound {lower}");
+                assert!(upper.is_none_or(|upper| size <= upper));
+            }
+        }
+    }
+
+    #[test]
+    fn test_size_batch() {
+        let sizer: Box<dyn Sizer> = Box::new(WordCounter);
+        let sizes = sizer.size_batch(&["hello, world!", "", "a b c"]).unwrap();
+        assert_eq!(sizes, vec![2, 0, 3]);
+    }
 }
@@ -0,0 +1,40 @@
+use crate::error::Result;
+use crate::sizer::Sizer;
+
+/// A marker struct for counting UTF-8 bytes in code chunks.
+///
+/// ```
+/// use code_splitter::{ByteCounter, Splitter};
+///
+/// let lang = tree_sitter_md::language();
+/// let splitter = Splitter::new(lang, ByteCounter).unwrap();
+///
+/// let code = b"hello, world!";
+/// let chunks = splitter.split(code).unwrap();
+/// ```
+pub struct ByteCounter;
+
+impl Sizer for ByteCounter {
+    /// Count the number of UTF-8 bytes in the given text.
+    fn size(&self, text: &str) -> Result<usize> {
+        Ok(text.len())
+    }
+
+    /// The number of bytes is known exactly.
+    fn size_hint(&self, text: &str) -> (usize, Option<usize>) {
+        (text.len(), Some(text.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size() {
+        let counter = ByteCounter;
+        let text = "café";
+        let size = counter.size(text).unwrap();
+        assert_eq!(size, 5);
+    }
+}
@@ -0,0 +1,306 @@
+use lru::LruCache;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, RandomState};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::error::{Error, Result};
+use crate::sizer::Sizer;
+
+/// Default number of sizes kept by a `CachedSizer`.
+const DEFAULT_CAPACITY: usize = 4096;
+
+/// A sizer that memoizes the sizes measured by another sizer.
+///
+/// Sizes are keyed by a 128-bit hash of the text and evicted in least-recently-used order once
+/// the capacity is reached. The cache is guarded by a mutex, so a `Splitter` with a `CachedSizer`
+/// can be shared across threads.
+///
+/// ```
+/// use code_splitter::{CachedSizer, CharCounter, Splitter};
+///
+/// let lang = tree_sitter_md::language();
+/// let sizer = CachedSizer::new(CharCounter).with_capacity(1024);
+/// let splitter = Splitter::new(lang, sizer).unwrap();
+///
+/// let code = b"hello, world!";
+/// let chunks = splitter.split(code).unwrap();
+/// ```
+pub struct CachedSizer<S: Sizer> {
+    /// Sizer for measuring texts missing from the cache.
+    sizer: S,
+    /// Sizes keyed by the hash of the text.
+    cache: Mutex<LruCache<u128, usize>>,
+    /// Two independently seeded hashers, whose hashes make up the 128-bit key.
+    hashers: [RandomState; 2],
+    /// Number of sizes found in the cache.
+    hits: AtomicU64,
+    /// Number of sizes measured by the sizer.
+    misses: AtomicU64,
+}
+
+/// Statistics of a `CachedSizer`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Number of sizes found in the cache.
+    pub hits: u64,
+    /// Number of sizes measured by the wrapped sizer.
+    pub misses: u64,
+    /// Number of sizes currently in the cache.
+    pub len: usize,
+    /// Maximum number of sizes kept in the cache.
+    pub capacity: usize,
+}
+
+impl CacheStats {
+    /// Fraction of lookups served from the cache, or 0 if there were none.
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / total as f64
+    }
+}
+
+impl<S> CachedSizer<S>
+where
+    S: Sizer,
+{
+    /// Wrap the sizer with a cache of 4096 sizes.
+    ///
+    /// The cache keeps a hash of each text, not the text itself, so every entry takes a few
+    /// dozen bytes however long the text is, and a full cache takes a few hundred KB.
+    pub fn new(sizer: S) -> Self {
+        Self {
+            sizer,
+            cache: Mutex::new(LruCache::new(non_zero(DEFAULT_CAPACITY))),
+            hashers: [RandomState::new(), RandomState::new()],
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Set the maximum number of sizes kept in the cache. A capacity of 0 is treated as 1.
+    ///
+    /// Entries have a fixed size of a few dozen bytes, so the memory of the cache grows with the
+    /// capacity alone, not with the length of the texts.
+    pub fn with_capacity(self, capacity: usize) -> Self {
+        self.lock().resize(non_zero(capacity));
+        self
+    }
+
+    /// Get the statistics of the cache.
+    pub fn stats(&self) -> CacheStats {
+        let cache = self.lock();
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            len: cache.len(),
+            capacity: cache.cap().get(),
+        }
+    }
+
+    /// Remove all sizes from the cache and reset the statistics.
+    pub fn clear(&self) {
+        self.lock().clear();
+        self.hits.store(0, Ordering::Relaxed);
+        self.misses.store(0, Ordering::Relaxed);
+    }
+
+    /// Get the wrapped sizer.
+    pub fn inner(&self) -> &S {
+        &self.sizer
+    }
+
+    /// Get the key of the text. With 128 bits, two texts practically never share a key.
+    fn key(&self, text: &str) -> u128 {
+        let [high, low] = &self.hashers;
+        (u128::from(high.hash_one(text)) << 64) | u128::from(low.hash_one(text))
+    }
+
+    fn lock(&self) -> MutexGuard<'_, LruCache<u128, usize>> {
+        // The cache only holds plain sizes, so it is still consistent after a panic
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<S> Sizer for CachedSizer<S>
+where
+    S: Sizer,
+{
+    /// Get the size of the text from the cache, or measure it with the wrapped sizer.
+    fn size(&self, text: &str) -> Result<usize> {
+        let key = self.key(text);
+        if let Some(&size) = self.lock().get(&key) {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(size);
+        }
+
+        // Measure without holding the lock, so other threads aren't blocked on slow sizers
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let size = self.sizer.size(text)?;
+        self.lock().put(key, size);
+        Ok(size)
+    }
+
+    /// Get the sizes of the texts from the cache, and measure the missing ones in one batch,
+    /// each distinct text once.
+    fn size_batch(&self, texts: &[&str]) -> Result<Vec<usize>> {
+        let keys = texts.iter().map(|text| self.key(text)).collect::<Vec<_>>();
+        let mut sizes = {
+            let mut cache = self.lock();
+            keys.iter()
+                .map(|key| cache.get(key).copied())
+                .collect::<Vec<_>>()
+        };
+
+        // Positions of every missing text, in the order the texts first appear
+        let mut missing = HashMap::<u128, Vec<usize>>::new();
+        let mut missing_keys = Vec::new();
+        let mut missing_texts = Vec::new();
+        for (i, (&text, &key)) in texts.iter().zip(&keys).enumerate() {
+            if sizes[i].is_none() {
+                missing
+                    .entry(key)
+                    .or_insert_with(|| {
+                        missing_keys.push(key);
+                        missing_texts.push(text);
+                        Vec::new()
+                    })
+                    .push(i);
+            }
+        }
+        self.hits.fetch_add(
+            (texts.len() - missing_texts.len()) as u64,
+            Ordering::Relaxed,
+        );
+        self.misses
+            .fetch_add(missing_texts.len() as u64, Ordering::Relaxed);
+
+        if !missing_texts.is_empty() {
+            let measured = self.sizer.size_batch(&missing_texts)?;
+            if measured.len() != missing_texts.len() {
+                let message = format!(
+                    "sizer returned {} sizes for {} texts",
+                    measured.len(),
+                    missing_texts.len()
+                );
+                return Err(Error::Sizer(message.into()));
+            }
+
+            let mut cache = self.lock();
+            for (key, size) in missing_keys.into_iter().zip(measured) {
+                cache.put(key, size);
+                for &i in &missing[&key] {
+                    sizes[i] = Some(size);
+                }
+            }
+        }
+
+        Ok(sizes.into_iter().flatten().collect())
+    }
+
+    fn size_hint(&self, text: &str) -> (usize, Option<usize>) {
+        self.sizer.size_hint(text)
+    }
+
+    fn tokenizer(&self) -> Option<&str> {
+        self.sizer.tokenizer()
+    }
+}
+
+fn non_zero(capacity: usize) -> NonZeroUsize {
+    NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sizer::CharCounter;
+    use std::sync::Arc;
+    use std::thread;
+
+    #[test]
+    fn test_hits_and_misses() {
+        let sizer = CachedSizer::new(CharCounter);
+        assert_eq!(sizer.size("café").unwrap(), 4);
+        assert_eq!(sizer.size("café").unwrap(), 4);
+        assert_eq!(sizer.size("hello").unwrap(), 5);
+
+        let stats = sizer.stats();
+        assert_eq!((stats.hits, stats.misses, stats.len), (1, 2, 2));
+        assert!((stats.hit_rate() - 1.0 / 3.0).abs() < f64::EPSILON);
+
+        sizer.clear();
+        assert_eq!(sizer.stats().len, 0);
+        assert_eq!(sizer.stats().hit_rate(), 0.0);
+    }
+
+    #[test]
+    fn test_size_batch() {
+        let sizer = CachedSizer::new(CharCounter);
+        sizer.size("café").unwrap();
+        let sizes = sizer.size_batch(&["hello", "café", "hello"]).unwrap();
+        assert_eq!(sizes, vec![5, 4, 5]);
+
+        let stats = sizer.stats();
+        assert_eq!((stats.hits, stats.misses, stats.len), (2, 2, 2));
+    }
+
+    /// A sizer that drops the last size of every batch.
+    struct ShortBatch;
+
+    impl Sizer for ShortBatch {
+        fn size(&self, text: &str) -> Result<usize> {
+            Ok(text.len())
+        }
+
+        fn size_batch(&self, texts: &[&str]) -> Result<Vec<usize>> {
+            Ok(texts[1..].iter().map(|text| text.len()).collect())
+        }
+    }
+
+    #[test]
+    fn test_size_batch_wrong_length() {
+        let sizer = CachedSizer::new(ShortBatch);
+        let err = sizer.size_batch(&["a", "bb"]).unwrap_err();
+        assert!(matches!(err, Error::Sizer(_)));
+        assert_eq!(sizer.stats().len, 0);
+    }
+
+    #[test]
+    fn test_eviction() {
+        let sizer = CachedSizer::new(CharCounter).with_capacity(2);
+        sizer.size("a").unwrap();
+        sizer.size("bb").unwrap();
+        // Touch "a" so that "bb" is the least recently used
+        sizer.size("a").unwrap();
+        sizer.size("ccc").unwrap();
+        sizer.size("a").unwrap();
+        sizer.size("bb").unwrap();
+
+        let stats = sizer.stats();
+        assert_eq!((stats.hits, stats.misses), (2, 4));
+        assert_eq!((stats.len, stats.capacity), (2, 2));
+    }
+
+    #[test]
+    fn test_shared_across_threads() {
+        let sizer = Arc::new(CachedSizer::new(CharCounter));
+        let handles = (0..4)
+            .map(|_| {
+                let sizer = Arc::clone(&sizer);
+                thread::spawn(move || sizer.size("hello, world!").unwrap())
+            })
+            .collect::<Vec<_>>();
+
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), 13);
+        }
+        let stats = sizer.stats();
+        assert_eq!(stats.hits + stats.misses, 4);
+        assert_eq!(stats.len, 1);
+    }
+}
@@ -19,6 +19,11 @@ impl Sizer for CharCounter {
     fn size(&self, text: &str) -> Result<usize> {
         Ok(text.chars().count())
     }
+
+    /// Every character takes one to four bytes.
+    fn size_hint(&self, text: &str) -> (usize, Option<usize>) {
+        (text.len().div_ceil(4), Some(text.len()))
+    }
 }
 
 #[cfg(test)]
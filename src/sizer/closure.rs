@@ -0,0 +1,59 @@
+use crate::error::Result;
+use crate::sizer::Sizer;
+
+/// A sizer that counts the size of code chunks with a closure.
+///
+/// A blanket implementation of `Sizer` for closures would conflict with the implementations for
+/// references and smart pointers, so closures are wrapped in `FnSizer` instead.
+///
+/// ```
+/// use code_splitter::{FnSizer, Sizer, Splitter};
+///
+/// let lang = tree_sitter_md::language();
+/// let sizer = FnSizer::new(|text: &str| Ok(text.len().div_ceil(4)));
+/// let splitter = Splitter::new(lang, sizer).unwrap();
+///
+/// let code = b"hello, world!";
+/// let chunks = splitter.split(code).unwrap();
+/// assert_eq!(chunks[0].size, 4);
+/// ```
+pub struct FnSizer<F>(F);
+
+impl<F> FnSizer<F>
+where
+    F: Fn(&str) -> Result<usize>,
+{
+    /// Wrap the closure as a sizer.
+    pub fn new(f: F) -> Self {
+        Self(f)
+    }
+}
+
+impl<F> Sizer for FnSizer<F>
+where
+    F: Fn(&str) -> Result<usize>,
+{
+    /// Count the size of the given text with the closure.
+    fn size(&self, text: &str) -> Result<usize> {
+        (self.0)(text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Error;
+
+    #[test]
+    fn test_size() {
+        let sizer = FnSizer::new(|text: &str| Ok(text.split(',').count()));
+        let size = sizer.size("a,b,c").unwrap();
+        assert_eq!(size, 3);
+    }
+
+    #[test]
+    fn test_error() {
+        let sizer = FnSizer::new(|_: &str| Err(Error::Sizer("quota exceeded".into())));
+        assert!(matches!(sizer.size("a"), Err(Error::Sizer(_))));
+    }
+}
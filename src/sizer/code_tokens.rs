@@ -0,0 +1,151 @@
+use crate::error::Result;
+use crate::sizer::Sizer;
+
+/// Operators counted as a single token, longest first so that the longest match wins.
+const OPERATORS: [&str; 34] = [
+    "<<=", ">>=", "**=", "//=", "===", "!==", "...", "::", "->", "=>", "==", "!=", "<=", ">=",
+    "&&", "||", "<<", ">>", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "++", "--", "**", "//",
+    "..", ":=", "?.", "??",
+];
+
+/// A marker struct for approximating the number of tokens a code model sees, without a
+/// tokenizer.
+///
+/// Identifiers are split into subwords at underscores and case changes, so
+/// `parseHttpResponseHeader` and `parse_http_response_header` both count 4. Numeric literals,
+/// operators such as `+=` and `->`, and other punctuation count one each. Whitespace is free.
+///
+/// ```
+/// use code_splitter::{CodeTokenCounter, Splitter};
+///
+/// let lang = tree_sitter_md::language();
+/// let splitter = Splitter::new(lang, CodeTokenCounter).unwrap();
+///
+/// let code = b"parseHttpResponseHeader(0x1F)";
+/// let chunks = splitter.split(code).unwrap();
+/// assert_eq!(chunks[0].size, 7);
+/// ```
+pub struct CodeTokenCounter;
+
+impl Sizer for CodeTokenCounter {
+    /// Count the number of code tokens in the given text.
+    fn size(&self, text: &str) -> Result<usize> {
+        let mut count = 0;
+        let mut rest = text;
+        while let Some(c) = rest.chars().next() {
+            let len = if c.is_whitespace() {
+                c.len_utf8()
+            } else if c.is_alphabetic() || c == '_' {
+                let len = rest
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                count += subwords(&rest[..len]);
+                len
+            } else if c.is_ascii_digit() {
+                count += 1;
+                number_len(rest)
+            } else {
+                count += 1;
+                OPERATORS
+                    .iter()
+                    .find(|op| rest.starts_with(*op))
+                    .map_or(c.len_utf8(), |op| op.len())
+            };
+            rest = &rest[len..];
+        }
+        Ok(count)
+    }
+
+    /// Every token covers at least one byte.
+    fn size_hint(&self, text: &str) -> (usize, Option<usize>) {
+        (0, Some(text.len()))
+    }
+}
+
+/// Count the subwords of an identifier. Subwords start after an underscore, at a lowercase to
+/// uppercase change, at the last capital of an acronym (`HTTPResponse` is `HTTP` and
+/// `Response`), and between letters and digits. Letters without case, such as CJK ideographs,
+/// count one each.
+fn subwords(ident: &str) -> usize {
+    let chars = ident.chars().collect::<Vec<_>>();
+    let is_cased = |c: char| c.is_uppercase() || c.is_lowercase();
+
+    let count = chars
+        .iter()
+        .enumerate()
+        .filter(|&(i, &c)| {
+            if c == '_' {
+                return false;
+            }
+            let Some(prev) = i.checked_sub(1).map(|i| chars[i]).filter(|&p| p != '_') else {
+                return true;
+            };
+            if c.is_numeric() {
+                !prev.is_numeric()
+            } else if !is_cased(c) {
+                true
+            } else if c.is_uppercase() {
+                let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_lowercase());
+                !prev.is_uppercase() || next_is_lower
+            } else {
+                !is_cased(prev)
+            }
+        })
+        .count();
+
+    // An identifier made of underscores alone is still a token
+    count.max(1)
+}
+
+/// Get the length of the numeric literal at the start of the text, including prefixes,
+/// suffixes, digit separators and a fraction, e.g. `0x1F`, `1_000u32` or `3.14`.
+fn number_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let mut len = 0;
+    while let Some(&b) = bytes.get(len) {
+        let is_fraction = b == b'.' && bytes.get(len + 1).is_some_and(u8::is_ascii_digit);
+        if !(b.is_ascii_alphanumeric() || b == b'_' || is_fraction) {
+            break;
+        }
+        len += 1;
+    }
+    len
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subwords() {
+        let cases = [
+            ("parseHttpResponseHeader", 4),
+            ("parse_http_response_header", 4),
+            ("HTTPResponse", 2),
+            ("utf8Decoder", 3),
+            ("MAX_SIZE", 2),
+            ("__init__", 1),
+            ("_", 1),
+            ("変数名", 3),
+        ];
+        for (ident, count) in cases {
+            assert_eq!(subwords(ident), count, "{ident}");
+        }
+    }
+
+    #[test]
+    fn test_size() {
+        let counter = CodeTokenCounter;
+        let cases = [
+            ("", 0),
+            ("x += 0x1F;", 4),
+            ("let total = 3.14 * 1_000u32;", 7),
+            ("fn parse(&self) -> Result<()> {}", 14),
+            ("a..b", 3),
+            ("if a <<= b { c }", 7),
+        ];
+        for (text, count) in cases {
+            assert_eq!(counter.size(text).unwrap(), count, "{text}");
+        }
+    }
+}
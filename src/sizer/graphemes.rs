@@ -0,0 +1,48 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::error::Result;
+use crate::sizer::Sizer;
+
+/// A marker struct for counting user-perceived characters (extended grapheme clusters) in code
+/// chunks.
+///
+/// Unlike `CharCounter`, an emoji made of several code points such as `👩‍💻`, or a letter with
+/// combining marks, counts as one.
+///
+/// ```
+/// use code_splitter::{GraphemeCounter, Splitter};
+///
+/// let lang = tree_sitter_md::language();
+/// let splitter = Splitter::new(lang, GraphemeCounter).unwrap();
+///
+/// let code = "hello, 👩‍💻!".as_bytes();
+/// let chunks = splitter.split(code).unwrap();
+/// assert_eq!(chunks[0].size, 9);
+/// ```
+pub struct GraphemeCounter;
+
+impl Sizer for GraphemeCounter {
+    /// Count the number of grapheme clusters in the given text.
+    fn size(&self, text: &str) -> Result<usize> {
+        Ok(text.graphemes(true).count())
+    }
+
+    /// Non-empty text has at least one grapheme cluster, and every cluster takes a byte or more.
+    fn size_hint(&self, text: &str) -> (usize, Option<usize>) {
+        (usize::from(!text.is_empty()), Some(text.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size() {
+        let counter = GraphemeCounter;
+        // Family emoji joined by zero-width joiners, a flag, and `e` with a combining acute
+        let text = "👨‍👩‍👧 🇯🇵 cafe\u{301}";
+        let size = counter.size(text).unwrap();
+        assert_eq!(size, 8);
+    }
+}
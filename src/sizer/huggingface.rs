@@ -1,20 +1,149 @@
+use std::path::Path;
 use tokenizers::Tokenizer;
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::sizer::Sizer;
 
+/// A sizer that counts tokens with a HuggingFace tokenizer, optionally including the special
+/// tokens the tokenizer adds around every input, such as `[CLS]` and `[SEP]`.
+///
+/// Counting the special tokens makes `max_size` match the real input budget of the model. The
+/// tokenizer can be loaded from the Hub, or offline from a `tokenizer.json` file or its bytes.
+///
+/// ```no_run
+/// use code_splitter::{HuggingfaceCounter, Splitter};
+///
+/// let lang = tree_sitter_md::language();
+/// let counter = HuggingfaceCounter::from_file("path/to/tokenizer.json")
+///     .unwrap()
+///     .with_special_tokens(true);
+/// let splitter = Splitter::new(lang, counter).unwrap().with_max_size(512);
+/// let chunks = splitter.split(b"hello, world!").unwrap();
+/// ```
+pub struct HuggingfaceCounter {
+    /// Tokenizer for counting tokens.
+    tokenizer: Tokenizer,
+    /// Whether to count the special tokens added by the tokenizer.
+    special_tokens: bool,
+    /// Identifier of the pretrained tokenizer on the Hub, if loaded from there.
+    name: Option<String>,
+}
+
+impl HuggingfaceCounter {
+    /// Wrap the tokenizer. Special tokens aren't counted by default.
+    pub fn new(tokenizer: Tokenizer) -> Self {
+        Self {
+            tokenizer,
+            special_tokens: false,
+            name: None,
+        }
+    }
+
+    /// Download a pretrained tokenizer from the HuggingFace Hub, e.g. `bert-base-cased`.
+    pub fn from_pretrained(identifier: &str) -> Result<Self> {
+        let tokenizer = Tokenizer::from_pretrained(identifier, None).map_err(Error::Sizer)?;
+        Ok(Self {
+            name: Some(identifier.to_string()),
+            ..Self::new(tokenizer)
+        })
+    }
+
+    /// Load a tokenizer from a local `tokenizer.json` file, without network access.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let tokenizer = Tokenizer::from_file(path).map_err(Error::Sizer)?;
+        Ok(Self::new(tokenizer))
+    }
+
+    /// Load a tokenizer from the bytes of a `tokenizer.json` file.
+    pub fn from_bytes(bytes: impl AsRef<[u8]>) -> Result<Self> {
+        let tokenizer = Tokenizer::from_bytes(bytes).map_err(Error::Sizer)?;
+        Ok(Self::new(tokenizer))
+    }
+
+    /// Set whether to count the special tokens the tokenizer adds to every input.
+    pub fn with_special_tokens(mut self, special_tokens: bool) -> Self {
+        self.special_tokens = special_tokens;
+        self
+    }
+
+    /// Get the wrapped tokenizer.
+    pub fn inner(&self) -> &Tokenizer {
+        &self.tokenizer
+    }
+}
+
+impl Sizer for HuggingfaceCounter {
+    /// Count the number of tokens in the given text.
+    fn size(&self, text: &str) -> Result<usize> {
+        count(&self.tokenizer, text, self.special_tokens)
+    }
+
+    /// Count the number of tokens in each text, encoding the texts in parallel.
+    fn size_batch(&self, texts: &[&str]) -> Result<Vec<usize>> {
+        count_batch(&self.tokenizer, texts, self.special_tokens)
+    }
+
+    fn tokenizer(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
 impl Sizer for Tokenizer {
     /// Count the number of tokens in the given text.
     fn size(&self, text: &str) -> Result<usize> {
-        let encoding = self.encode(text, false)?;
-        Ok(encoding.get_ids().len())
+        count(self, text, false)
+    }
+
+    /// Count the number of tokens in each text, encoding the texts in parallel.
+    fn size_batch(&self, texts: &[&str]) -> Result<Vec<usize>> {
+        count_batch(self, texts, false)
     }
 }
 
+fn count(tokenizer: &Tokenizer, text: &str, special_tokens: bool) -> Result<usize> {
+    let encoding = tokenizer
+        .encode(text, special_tokens)
+        .map_err(Error::Sizer)?;
+    Ok(encoding.get_ids().len())
+}
+
+fn count_batch(tokenizer: &Tokenizer, texts: &[&str], special_tokens: bool) -> Result<Vec<usize>> {
+    let encodings = tokenizer
+        .encode_batch(texts.to_vec(), special_tokens)
+        .map_err(Error::Sizer)?;
+    Ok(encodings.iter().map(|e| e.get_ids().len()).collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const TOKENIZER_FILE: &str = "tests/testdata/tokenizer.json";
+
+    #[test]
+    fn test_counter_from_file() {
+        let counter = HuggingfaceCounter::from_file(TOKENIZER_FILE).unwrap();
+        // ["hello", ",", "world", "!"]
+        assert_eq!(counter.size("Hello, world!").unwrap(), 4);
+        assert_eq!(counter.tokenizer(), None);
+
+        let counter = counter.with_special_tokens(true);
+        // ["[CLS]", "hello", ",", "world", "!", "[SEP]"]
+        assert_eq!(counter.size("Hello, world!").unwrap(), 6);
+        let sizes = counter.size_batch(&["hello", "hello world"]).unwrap();
+        assert_eq!(sizes, vec![3, 4]);
+    }
+
+    #[test]
+    fn test_counter_from_bytes() {
+        let bytes = std::fs::read(TOKENIZER_FILE).unwrap();
+        let counter = HuggingfaceCounter::from_bytes(bytes).unwrap();
+        assert_eq!(counter.size("# hello").unwrap(), 2);
+
+        let err = HuggingfaceCounter::from_bytes(b"{}").err().unwrap();
+        assert!(matches!(err, Error::Sizer(_)));
+    }
+
     #[test]
     fn test_tokenizer_size() {
         let tokenizer = Tokenizer::from_pretrained("bert-base-cased", None).unwrap();
@@ -22,5 +151,8 @@ mod tests {
         let size = tokenizer.size(text).unwrap();
         // ["Hello", ",", "y", "'", "all", "!", "How", "are", "you", "[UNK]", "?"]
         assert_eq!(size, 11);
+
+        let sizes = tokenizer.size_batch(&[text, "Hello"]).unwrap();
+        assert_eq!(sizes, vec![11, 1]);
     }
 }
@@ -0,0 +1,40 @@
+use crate::error::Result;
+use crate::sizer::Sizer;
+
+/// A marker struct for counting lines in code chunks.
+///
+/// ```
+/// use code_splitter::{LineCounter, Splitter};
+///
+/// let lang = tree_sitter_md::language();
+/// let splitter = Splitter::new(lang, LineCounter).unwrap();
+///
+/// let code = b"hello,\nworld!";
+/// let chunks = splitter.split(code).unwrap();
+/// ```
+pub struct LineCounter;
+
+impl Sizer for LineCounter {
+    /// Count the number of lines in the given text.
+    fn size(&self, text: &str) -> Result<usize> {
+        Ok(text.lines().count())
+    }
+
+    /// Non-empty text has at least one line, and every line but the last ends with a byte.
+    fn size_hint(&self, text: &str) -> (usize, Option<usize>) {
+        (usize::from(!text.is_empty()), Some(text.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size() {
+        let counter = LineCounter;
+        let text = "fn main() {\r\n    hello();\n}\n";
+        let size = counter.size(text).unwrap();
+        assert_eq!(size, 3);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::error::Result;
+use crate::sizer::Sizer;
+
+/// A sizer that measures text with another sizer after normalizing its whitespace.
+///
+/// Leading indentation and trailing whitespace are removed from every line, runs of whitespace
+/// within a line are collapsed to a single space, and blank lines are dropped. Deeply indented
+/// code then counts about as much as it would after dedenting, while the chunk ranges returned
+/// by `Splitter` still cover the original text.
+///
+/// ```
+/// use code_splitter::{CharCounter, NormalizedSizer, Splitter};
+///
+/// let lang = tree_sitter_md::language();
+/// let splitter = Splitter::new(lang, NormalizedSizer::new(CharCounter)).unwrap();
+///
+/// let code = b"        hello,    world!";
+/// let chunks = splitter.split(code).unwrap();
+/// assert_eq!(chunks[0].size, 13);
+/// ```
+pub struct NormalizedSizer<S: Sizer> {
+    /// Sizer for measuring the normalized text.
+    sizer: S,
+}
+
+impl<S> NormalizedSizer<S>
+where
+    S: Sizer,
+{
+    /// Wrap the sizer to measure whitespace-normalized text.
+    pub fn new(sizer: S) -> Self {
+        Self { sizer }
+    }
+
+    /// Get the wrapped sizer.
+    pub fn inner(&self) -> &S {
+        &self.sizer
+    }
+}
+
+impl<S> Sizer for NormalizedSizer<S>
+where
+    S: Sizer,
+{
+    /// Measure the size of the text after normalizing its whitespace.
+    fn size(&self, text: &str) -> Result<usize> {
+        self.sizer.size(&normalize(text))
+    }
+
+    /// Measure the sizes of the texts after normalizing their whitespace, in one batch.
+    fn size_batch(&self, texts: &[&str]) -> Result<Vec<usize>> {
+        let normalized = texts.iter().map(|text| normalize(text)).collect::<Vec<_>>();
+        let normalized = normalized.iter().map(String::as_str).collect::<Vec<_>>();
+        self.sizer.size_batch(&normalized)
+    }
+
+    fn tokenizer(&self) -> Option<&str> {
+        self.sizer.tokenizer()
+    }
+}
+
+/// Strip indentation, collapse runs of whitespace and drop blank lines.
+fn normalize(text: &str) -> String {
+    let mut normalized = String::with_capacity(text.len());
+    for line in text.lines() {
+        let mut words = line.split_whitespace();
+        let Some(first) = words.next() else {
+            continue;
+        };
+
+        if !normalized.is_empty() {
+            normalized.push('\n');
+        }
+        normalized.push_str(first);
+        for word in words {
+            normalized.push(' ');
+            normalized.push_str(word);
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sizer::CharCounter;
+
+    #[test]
+    fn test_normalize() {
+        let text = "fn main() {\n\n        let  x =\t1;\n    }  \n";
+        assert_eq!(normalize(text), "fn main() {\nlet x = 1;\n}");
+    }
+
+    #[test]
+    fn test_size() {
+        let sizer = NormalizedSizer::new(CharCounter);
+        let text = "            return  café;";
+        let size = sizer.size(text).unwrap();
+        assert_eq!(size, 12);
+    }
+}
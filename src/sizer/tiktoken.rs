@@ -1,20 +1,186 @@
-use tiktoken_rs::CoreBPE;
+use std::num::NonZeroUsize;
+use std::panic;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use tiktoken_rs::tokenizer::{get_tokenizer, Tokenizer};
+use tiktoken_rs::{get_bpe_from_tokenizer, CoreBPE};
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::sizer::Sizer;
 
+/// Minimum number of bytes in a batch before it is encoded on several threads. Smaller batches
+/// are encoded faster than the threads can be spawned.
+const MIN_PARALLEL_BYTES: usize = 64 * 1024;
+
+/// Number of helper threads encoding batches, across all batches in the process.
+static HELPERS: AtomicUsize = AtomicUsize::new(0);
+
+/// Names of the tiktoken encodings, as used by OpenAI's `tiktoken` library.
+const ENCODINGS: [(&str, Tokenizer); 6] = [
+    ("o200k_base", Tokenizer::O200kBase),
+    ("cl100k_base", Tokenizer::Cl100kBase),
+    ("p50k_base", Tokenizer::P50kBase),
+    ("p50k_edit", Tokenizer::P50kEdit),
+    ("r50k_base", Tokenizer::R50kBase),
+    ("gpt2", Tokenizer::Gpt2),
+];
+
+/// A sizer that counts tokens with a tiktoken encoding chosen by name or by model.
+///
+/// It counts the same tokens as the `CoreBPE` it wraps, but also knows the name of its encoding,
+/// which `Splitter` records in [`Chunk::tokenizer`](crate::Chunk::tokenizer).
+///
+/// ```
+/// use code_splitter::{Splitter, TiktokenCounter};
+///
+/// let lang = tree_sitter_md::language();
+/// let counter = TiktokenCounter::for_model("gpt-4o").unwrap();
+/// let splitter = Splitter::new(lang, counter).unwrap();
+///
+/// let chunks = splitter.split(b"hello, world!").unwrap();
+/// assert_eq!(chunks[0].tokenizer.as_deref(), Some("o200k_base"));
+/// ```
+pub struct TiktokenCounter {
+    /// Tokenizer of the encoding.
+    bpe: CoreBPE,
+    /// Name of the encoding, e.g. `cl100k_base`.
+    encoding: &'static str,
+}
+
+impl TiktokenCounter {
+    /// Load the encoding with the given name: `o200k_base`, `cl100k_base`, `p50k_base`,
+    /// `p50k_edit`, `r50k_base` or `gpt2`.
+    pub fn new(encoding: &str) -> Result<Self> {
+        let (encoding, tokenizer) = ENCODINGS
+            .into_iter()
+            .find(|(name, _)| *name == encoding)
+            .ok_or_else(|| {
+                Error::InvalidArgument(format!("unknown tiktoken encoding {encoding}"))
+            })?;
+        let bpe = get_bpe_from_tokenizer(tokenizer).map_err(|e| Error::Sizer(e.into()))?;
+        Ok(Self { bpe, encoding })
+    }
+
+    /// Load the encoding used by the given OpenAI model, e.g. `gpt-4o` or `text-davinci-003`.
+    pub fn for_model(model: &str) -> Result<Self> {
+        let tokenizer = get_tokenizer(model).ok_or_else(|| {
+            Error::InvalidArgument(format!("no tiktoken encoding for model {model}"))
+        })?;
+        let (encoding, _) = ENCODINGS
+            .into_iter()
+            .find(|(_, t)| *t == tokenizer)
+            .expect("every tokenizer has an encoding name");
+        Self::new(encoding)
+    }
+
+    /// Get the name of the encoding.
+    pub fn encoding(&self) -> &'static str {
+        self.encoding
+    }
+
+    /// Get the tokenizer of the encoding.
+    pub fn bpe(&self) -> &CoreBPE {
+        &self.bpe
+    }
+}
+
+impl Sizer for TiktokenCounter {
+    fn size(&self, text: &str) -> Result<usize> {
+        self.bpe.size(text)
+    }
+
+    fn size_batch(&self, texts: &[&str]) -> Result<Vec<usize>> {
+        self.bpe.size_batch(texts)
+    }
+
+    fn size_hint(&self, text: &str) -> (usize, Option<usize>) {
+        self.bpe.size_hint(text)
+    }
+
+    fn tokenizer(&self) -> Option<&str> {
+        Some(self.encoding)
+    }
+}
+
 impl Sizer for CoreBPE {
     /// Count the number of tokens in the given text.
     fn size(&self, text: &str) -> Result<usize> {
         let tokens = self.encode_with_special_tokens(text);
         Ok(tokens.len())
     }
+
+    /// Every token covers at least one byte, and the pre-tokenizer never merges two
+    /// whitespace-separated words into one token.
+    fn size_hint(&self, text: &str) -> (usize, Option<usize>) {
+        (text.split_whitespace().count(), Some(text.len()))
+    }
+
+    /// Count the number of tokens in each text, encoding large batches on helper threads.
+    ///
+    /// All batches in the process share one budget of helper threads, one less than the
+    /// available parallelism. Batches sized while the budget is spent, e.g. from the threads of
+    /// a caller that already splits files in parallel, are encoded on the calling thread.
+    fn size_batch(&self, texts: &[&str]) -> Result<Vec<usize>> {
+        let count = |texts: &[&str]| {
+            texts
+                .iter()
+                .map(|text| self.encode_with_special_tokens(text).len())
+                .collect::<Vec<_>>()
+        };
+
+        let bytes = texts.iter().map(|text| text.len()).sum::<usize>();
+        if texts.len() < 2 || bytes < MIN_PARALLEL_BYTES {
+            return Ok(count(texts));
+        }
+        let helpers = Helpers::reserve(texts.len() - 1);
+        if helpers.count == 0 {
+            return Ok(count(texts));
+        }
+
+        let mut parts = texts.chunks(texts.len().div_ceil(helpers.count + 1));
+        let first = parts.next().unwrap_or_default();
+        let sizes = thread::scope(|scope| {
+            let handles = parts
+                .map(|texts| scope.spawn(move || count(texts)))
+                .collect::<Vec<_>>();
+            let mut sizes = count(first);
+            for handle in handles {
+                sizes.extend(handle.join().unwrap_or_else(|e| panic::resume_unwind(e)));
+            }
+            sizes
+        });
+        Ok(sizes)
+    }
+}
+
+/// Helper threads reserved from the process-wide budget, returned when dropped.
+struct Helpers {
+    count: usize,
+}
+
+impl Helpers {
+    /// Reserve up to `wanted` helper threads, or none if the budget is spent.
+    fn reserve(wanted: usize) -> Self {
+        let budget = thread::available_parallelism().map_or(1, NonZeroUsize::get) - 1;
+        let mut count = 0;
+        let _ = HELPERS.fetch_update(Ordering::AcqRel, Ordering::Acquire, |busy| {
+            count = wanted.min(budget.saturating_sub(busy));
+            Some(busy + count)
+        });
+        Helpers { count }
+    }
+}
+
+impl Drop for Helpers {
+    fn drop(&mut self) {
+        HELPERS.fetch_sub(self.count, Ordering::AcqRel);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use tiktoken_rs::cl100k_base;
+    use tiktoken_rs::{cl100k_base, o200k_base, p50k_base, r50k_base};
 
     #[test]
     fn test_core_bpe_size() {
@@ -23,4 +189,77 @@ mod tests {
         let size = bpe.size(text).unwrap();
         assert_eq!(size, 9);
     }
+
+    #[test]
+    fn test_tiktoken_counter() {
+        let counter = TiktokenCounter::new("p50k_base").unwrap();
+        assert_eq!(counter.encoding(), "p50k_base");
+        assert_eq!(counter.tokenizer(), Some("p50k_base"));
+
+        let cases = [
+            ("gpt-4o", "o200k_base"),
+            ("gpt-4-0613", "cl100k_base"),
+            ("text-davinci-003", "p50k_base"),
+            ("davinci", "r50k_base"),
+        ];
+        for (model, encoding) in cases {
+            let counter = TiktokenCounter::for_model(model).unwrap();
+            assert_eq!(counter.encoding(), encoding, "{model}");
+        }
+
+        let text = "I can feel the magic, can you?";
+        let counter = TiktokenCounter::new("cl100k_base").unwrap();
+        assert_eq!(counter.size(text).unwrap(), 9);
+    }
+
+    #[test]
+    fn test_tiktoken_counter_unknown() {
+        let err = TiktokenCounter::new("cl200k_base").err().unwrap();
+        assert!(matches!(err, Error::InvalidArgument(_)));
+        assert!(err.to_string().contains("cl200k_base"));
+        assert!(TiktokenCounter::for_model("llama-3").is_err());
+    }
+
+    #[test]
+    fn test_core_bpe_size_hint() {
+        let texts = [
+            "",
+            "I can feel the magic, can you?",
+            "  fn main() {\n\tprintln!(\"héllo 😁\");\n}\n",
+            "a<|endoftext|>b <|endoftext|>",
+            "x  =  [1,2,3]   // 日本語",
+        ];
+        for bpe in [r50k_base(), p50k_base(), cl100k_base(), o200k_base()] {
+            let bpe = bpe.unwrap();
+            for text in texts {
+                let size = bpe.size(text).unwrap();
+                let (lower, upper) = bpe.size_hint(text);
+                assert!(lower <= size && size <= upper.unwrap(), "{text:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_core_bpe_size_batch() {
+        let bpe = cl100k_base().unwrap();
+        let long = "I can feel the magic, can you? ".repeat(4096);
+        let mut texts = vec!["I can feel the magic, can you?"; 7];
+        texts.push(&long);
+
+        let sizes = bpe.size_batch(&texts).unwrap();
+        let expected = texts
+            .iter()
+            .map(|text| bpe.size(text).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, expected);
+        assert_eq!(sizes[0], 9);
+    }
+
+    #[test]
+    fn test_helpers_budget() {
+        let budget = thread::available_parallelism().map_or(1, NonZeroUsize::get) - 1;
+        let all = Helpers::reserve(usize::MAX);
+        assert!(all.count <= budget);
+        assert_eq!(Helpers::reserve(0).count, 0);
+    }
 }
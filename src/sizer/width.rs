@@ -0,0 +1,46 @@
+use unicode_width::UnicodeWidthStr;
+
+use crate::error::Result;
+use crate::sizer::Sizer;
+
+/// A marker struct for counting the display width of code chunks in terminal columns.
+///
+/// East Asian wide characters and emoji take two columns, and combining marks take none. The
+/// width of every line is summed, without counting the line breaks.
+///
+/// ```
+/// use code_splitter::{DisplayWidthCounter, Splitter};
+///
+/// let lang = tree_sitter_md::language();
+/// let splitter = Splitter::new(lang, DisplayWidthCounter).unwrap();
+///
+/// let code = "こんにちは".as_bytes();
+/// let chunks = splitter.split(code).unwrap();
+/// assert_eq!(chunks[0].size, 10);
+/// ```
+pub struct DisplayWidthCounter;
+
+impl Sizer for DisplayWidthCounter {
+    /// Count the number of columns the given text takes in a terminal.
+    fn size(&self, text: &str) -> Result<usize> {
+        Ok(text.lines().map(UnicodeWidthStr::width).sum())
+    }
+
+    /// Wide characters take at least three bytes, so no character is wider than its encoding.
+    fn size_hint(&self, text: &str) -> (usize, Option<usize>) {
+        (0, Some(text.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_size() {
+        let counter = DisplayWidthCounter;
+        let text = "日本語 ok\n😁e\u{301}";
+        let size = counter.size(text).unwrap();
+        assert_eq!(size, 6 + 3 + 2 + 1);
+    }
+}
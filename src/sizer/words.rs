@@ -19,6 +19,11 @@ impl Sizer for WordCounter {
     fn size(&self, text: &str) -> Result<usize> {
         Ok(text.split_whitespace().count())
     }
+
+    /// Words are separated by whitespace, so at most every other byte starts a word.
+    fn size_hint(&self, text: &str) -> (usize, Option<usize>) {
+        (0, Some(text.len().div_ceil(2)))
+    }
 }
 
 #[cfg(test)]
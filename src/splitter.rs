@@ -1,9 +1,13 @@
 use crate::chunk::Chunk;
-use crate::error::Result;
+use crate::encoding::Source;
+use crate::error::{Error, Result};
 use crate::sizer::Sizer;
+use crate::weights::{weighted_sum, NodeWeights};
 
+use encoding_rs::{Encoding, UTF_8};
+use std::collections::HashMap;
 use std::str;
-use tree_sitter::{Language, Node, Parser};
+use tree_sitter::{Language, Node, Parser, Tree};
 
 /// Default maximum size of a chunk.
 const DEFAULT_MAX_SIZE: usize = 512;
@@ -16,6 +20,12 @@ pub struct Splitter<T: Sizer> {
     sizer: T,
     /// Maximum size of a code chunk.
     max_size: usize,
+    /// Additional sizers and maximum sizes that every code chunk must satisfy.
+    limits: Vec<(Box<dyn Sizer + Send + Sync>, usize)>,
+    /// Encoding of the code, detected for each input if not set.
+    encoding: Option<&'static Encoding>,
+    /// Weights of the sizes of nodes by kind.
+    weights: HashMap<String, f64>,
 }
 
 impl<T> Splitter<T>
@@ -71,15 +81,35 @@ where
     /// ```
     pub fn new(language: Language, sizer: T) -> Result<Self> {
         // Ensure tree-sitter-<language> crate can be loaded
-        Parser::new().set_language(&language)?;
+        parse(&language, b"")?;
 
         Ok(Self {
             language,
             sizer,
             max_size: DEFAULT_MAX_SIZE,
+            limits: Vec::new(),
+            encoding: None,
+            weights: HashMap::new(),
         })
     }
 
+    /// Create a new `Splitter` for a bundled language. The grammar of the language must be
+    /// enabled with its cargo feature, e.g. `lang-rust`.
+    ///
+    /// # Example: split rust code by characters
+    /// ```
+    /// # #[cfg(feature = "lang-rust")]
+    /// # {
+    /// use code_splitter::{CharCounter, Language, Splitter};
+    ///
+    /// let splitter = Splitter::for_language(Language::Rust, CharCounter).unwrap();
+    /// let chunks = splitter.split(b"fn main() {}").unwrap();
+    /// # }
+    /// ```
+    pub fn for_language(language: crate::Language, sizer: T) -> Result<Self> {
+        Self::new(language.tree_sitter_language(), sizer)
+    }
+
     /// Set the maximum size of a chunk. The default is 512.
     ///
     /// # Example: set the maximum size to 256
@@ -97,57 +127,175 @@ where
         self
     }
 
+    /// Add a limit that every chunk must satisfy in addition to `max_size`. The sizes measured
+    /// for each limit are reported in [`Chunk::sizes`], after the size measured by the splitter's
+    /// own sizer.
+    ///
+    /// # Example: limit chunks to 512 characters and 20 lines
+    /// ```
+    /// use code_splitter::{CharCounter, LineCounter, Splitter};
+    ///
+    /// let lang = tree_sitter_md::language();
+    /// let splitter = Splitter::new(lang, CharCounter)
+    ///   .unwrap()
+    ///   .with_max_size(512)
+    ///   .with_limit(LineCounter, 20);
+    /// let chunks = splitter.split(b"hello, world!").unwrap();
+    /// assert_eq!(chunks[0].sizes, vec![13, 1]);
+    /// ```
+    pub fn with_limit<S>(mut self, sizer: S, max_size: usize) -> Self
+    where
+        S: Sizer + Send + Sync + 'static,
+    {
+        self.limits.push((Box::new(sizer), max_size));
+        self
+    }
+
+    /// Set the encoding of the code. By default the encoding is detected from a byte order mark
+    /// or a declaration such as `# -*- coding: latin-1 -*-`, falling back to UTF-8.
+    ///
+    /// The encoding set here takes precedence over both. A byte order mark at the start of the
+    /// code is skipped and left out of every chunk, even if it is the mark of another encoding.
+    ///
+    /// Code in other encodings is decoded to UTF-8 for parsing and sizing, but the ranges of the
+    /// chunks always point at the original bytes.
+    ///
+    /// # Example: split UTF-16 code
+    /// ```
+    /// use code_splitter::{CharCounter, Encoding, Splitter};
+    ///
+    /// let lang = tree_sitter_md::language();
+    /// let encoding = Encoding::for_label(b"utf-16le").unwrap();
+    /// let splitter = Splitter::new(lang, CharCounter)
+    ///   .unwrap()
+    ///   .with_encoding(encoding);
+    /// let chunks = splitter.split(b"h\0i\0").unwrap();
+    /// assert_eq!(chunks[0].range.end_byte, 4);
+    /// ```
+    pub fn with_encoding(mut self, encoding: &'static Encoding) -> Self {
+        self.encoding = Some(encoding);
+        self
+    }
+
+    /// Weight the size of nodes by their kind, e.g. to count comments less than executable code.
+    ///
+    /// The size of a chunk becomes the sum of the sizes of its pieces, each multiplied by the
+    /// weight of the innermost node covering it, rounded to the nearest integer. Node kinds
+    /// without a weight count fully. Weights apply to the splitter's own sizer, not to the
+    /// sizers added with [`Splitter::with_limit`]. Since a sum of sizes of pieces isn't exactly
+    /// the size of the whole for tokenizers, weighted sizes are approximate.
+    ///
+    /// # Example: count comments half
+    /// ```
+    /// use code_splitter::{CharCounter, Splitter};
+    ///
+    /// let lang = tree_sitter_rust::language();
+    /// let splitter = Splitter::new(lang, CharCounter)
+    ///   .unwrap()
+    ///   .with_weights([("line_comment", 0.5)]);
+    /// let chunks = splitter.split(b"// comment\nfn main() {}").unwrap();
+    /// assert_eq!(chunks[0].size, 5 + 1 + 12);
+    /// ```
+    pub fn with_weights<I, K>(mut self, weights: I) -> Self
+    where
+        I: IntoIterator<Item = (K, f64)>,
+        K: Into<String>,
+    {
+        self.weights.extend(
+            weights
+                .into_iter()
+                .map(|(kind, weight)| (kind.into(), weight)),
+        );
+        self
+    }
+
     /// Split the code into chunks with no larger than `max_size`.
     pub fn split(&self, code: &[u8]) -> Result<Vec<Chunk>> {
         if code.is_empty() {
             return Ok(vec![]);
         }
 
-        let mut parser = Parser::new();
-        parser
-            .set_language(&self.language)
-            .expect("Error loading tree-sitter language");
-        let tree = parser.parse(code, None).ok_or("Error parsing code")?;
+        let source = Source::new(code, self.encoding);
+        let tree = parse(&self.language, &source.text)?;
         let root_node = tree.root_node();
+        let weights =
+            (!self.weights.is_empty()).then(|| NodeWeights::resolve(&self.weights, &root_node));
+        let weights = weights.as_ref();
+        let root_span = (root_node, root_node.start_byte(), root_node.end_byte());
+        let root_size = self.measure(&[root_span], &source.text, weights)?[0];
 
-        let chunks = self.split_node(&root_node, 0, code)?;
+        let mut chunks = self.split_node(&root_node, 0, &source.text, weights, root_size)?;
+        self.measure_joined(&mut chunks, &source.text)?;
+
+        let chunks = chunks
+            .into_iter()
+            // Point the chunks at the original bytes
+            .map(|chunk| Chunk {
+                range: source.original_range(chunk.range),
+                encoding: source.encoding,
+                tokenizer: self.sizer.tokenizer().map(str::to_string),
+                ..chunk
+            })
+            .collect();
 
         Ok(chunks)
     }
 
-    fn split_node(&self, node: &Node, depth: usize, code: &[u8]) -> Result<Vec<Chunk>> {
+    /// Split the node of the given size, recursing into its children if it is too large. A size
+    /// of `None` means the size hint already showed the node to be larger than `max_size`.
+    fn split_node(
+        &self,
+        node: &Node,
+        depth: usize,
+        code: &[u8],
+        weights: Option<&NodeWeights>,
+        chunk_size: Option<usize>,
+    ) -> Result<Vec<Chunk>> {
         let text = node.utf8_text(code)?;
-        let chunk_size = self.sizer.size(text)?;
 
-        if chunk_size == 0 {
-            return Ok(vec![]);
-        }
+        if let Some(chunk_size) = chunk_size {
+            if chunk_size == 0 {
+                return Ok(vec![]);
+            }
 
-        if chunk_size <= self.max_size {
-            return Ok(vec![Chunk {
-                subtree: format!("{}: {}", format_node(node, depth), chunk_size),
-                range: node.range(),
-                size: chunk_size,
-            }]);
+            if let Some(sizes) = self.fit(chunk_size, text)? {
+                return Ok(vec![Chunk {
+                    subtree: format!("{}: {}", format_node(node, depth), chunk_size),
+                    range: node.range(),
+                    size: chunk_size,
+                    sizes,
+                    encoding: UTF_8,
+                    tokenizer: None,
+                }]);
+            }
         }
 
-        let chunks = node
+        let children = node.children(&mut node.walk()).collect::<Vec<_>>();
+        let spans = children
+            .iter()
+            .map(|child| (*child, child.start_byte(), child.end_byte()))
+            .collect::<Vec<_>>();
+        // Size all children at once, which is much faster for tokenizers
+        let sizes = self.measure(&spans, code, weights)?;
+
+        let chunks = children
+            .iter()
+            .zip(sizes)
             // Traverse the children in depth-first order
-            .children(&mut node.walk())
-            .map(|child| self.split_node(&child, depth + 1, code))
+            .map(|(child, size)| self.split_node(child, depth + 1, code, weights, size))
             .collect::<Result<Vec<_>>>()?
             .into_iter()
             // Join the tail and head of neighboring chunks if possible
             .try_fold(Vec::new(), |mut acc, mut next| -> Result<Vec<Chunk>> {
                 if let Some(tail) = acc.pop() {
                     if let Some(head) = next.first_mut() {
-                        let joined_size = self.joined_size(&tail, head, code)?;
-                        if joined_size <= self.max_size {
+                        if let Some(sizes) = self.joined_sizes(node, &tail, head, code, weights)? {
                             // Concatenate the tail and head names
                             head.subtree = format!("{}\n{}", tail.subtree, head.subtree);
                             head.range.start_byte = tail.range.start_byte;
                             head.range.start_point = tail.range.start_point;
-                            head.size = joined_size;
+                            head.size = sizes.first().copied().unwrap_or_default();
+                            head.sizes = sizes;
                         } else {
                             acc.push(tail);
                         }
@@ -163,11 +311,179 @@ where
         Ok(chunks)
     }
 
-    fn joined_size(&self, chunk: &Chunk, next: &Chunk, code: &[u8]) -> Result<usize> {
-        let joined_bytes = &code[chunk.range.start_byte..next.range.end_byte];
-        let joined_text = str::from_utf8(joined_bytes)?;
-        self.sizer.size(joined_text)
+    /// Measure the spans in one batch, except those whose size hint already exceeds `max_size`.
+    /// Each span is a node and the byte range within it to measure.
+    fn measure(
+        &self,
+        spans: &[(Node, usize, usize)],
+        code: &[u8],
+        weights: Option<&NodeWeights>,
+    ) -> Result<Vec<Option<usize>>> {
+        let Some(weights) = weights else {
+            let texts = spans
+                .iter()
+                .map(|&(_, start, end)| str::from_utf8(&code[start..end]))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+
+            let mut sizes = vec![None; texts.len()];
+            let (indices, small): (Vec<_>, Vec<_>) = texts
+                .iter()
+                .enumerate()
+                .filter(|(_, text)| self.sizer.size_hint(text).0 <= self.max_size)
+                .unzip();
+            for (i, size) in indices.into_iter().zip(self.sizer.size_batch(&small)?) {
+                sizes[i] = Some(size);
+            }
+            return Ok(sizes);
+        };
+
+        // Size the pieces of every span in one batch, then sum them per span
+        let mut segments = Vec::new();
+        let mut counts = Vec::with_capacity(spans.len());
+        for &(node, start, end) in spans {
+            let len = segments.len();
+            weights.segments(&node, start, end, code, &mut segments)?;
+            counts.push(segments.len() - len);
+        }
+        let (texts, factors): (Vec<_>, Vec<_>) = segments.into_iter().unzip();
+        let sizes = self.sizer.size_batch(&texts)?;
+
+        let mut offset = 0;
+        let sizes = counts
+            .into_iter()
+            .map(|count| {
+                let range = offset..offset + count;
+                offset += count;
+                Some(weighted_sum(&sizes[range.clone()], &factors[range]))
+            })
+            .collect();
+        Ok(sizes)
+    }
+
+    /// Get the sizes of the joined chunks within the node if they satisfy every limit. The
+    /// sizes are left empty when the size hints alone show that the chunks fit, to be measured
+    /// once the chunks are final by `measure_joined`.
+    fn joined_sizes(
+        &self,
+        node: &Node,
+        chunk: &Chunk,
+        next: &Chunk,
+        code: &[u8],
+        weights: Option<&NodeWeights>,
+    ) -> Result<Option<Vec<usize>>> {
+        let (start, end) = (chunk.range.start_byte, next.range.end_byte);
+        let joined_text = str::from_utf8(&code[start..end])?;
+
+        // Size hints don't account for weights
+        if weights.is_none() {
+            match self.fit_hint(joined_text) {
+                Some(true) => return Ok(Some(Vec::new())),
+                Some(false) => return Ok(None),
+                None => {}
+            }
+        }
+
+        let Some(joined_size) = self.measure(&[(*node, start, end)], code, weights)?[0] else {
+            return Ok(None);
+        };
+        self.fit(joined_size, joined_text)
+    }
+
+    /// Measure the chunks that were joined on size hints alone.
+    fn measure_joined(&self, chunks: &mut [Chunk], code: &[u8]) -> Result<()> {
+        let mut unmeasured = chunks
+            .iter_mut()
+            .filter(|chunk| chunk.sizes.is_empty())
+            .collect::<Vec<_>>();
+        if unmeasured.is_empty() {
+            return Ok(());
+        }
+
+        let texts = unmeasured
+            .iter()
+            .map(|chunk| str::from_utf8(&code[chunk.range.start_byte..chunk.range.end_byte]))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let mut sizes = self
+            .sizer
+            .size_batch(&texts)?
+            .into_iter()
+            .map(|size| vec![size])
+            .collect::<Vec<_>>();
+        for (sizer, _) in &self.limits {
+            for (sizes, size) in sizes.iter_mut().zip(sizer.size_batch(&texts)?) {
+                sizes.push(size);
+            }
+        }
+
+        for (chunk, sizes) in unmeasured.iter_mut().zip(sizes) {
+            chunk.size = sizes[0];
+            chunk.sizes = sizes;
+        }
+        Ok(())
+    }
+
+    /// Decide from the size hints alone whether the text satisfies every limit. Returns `None`
+    /// if the text has to be measured to tell.
+    fn fit_hint(&self, text: &str) -> Option<bool> {
+        let limits = self
+            .limits
+            .iter()
+            .map(|(sizer, max_size)| (sizer.size_hint(text), *max_size));
+        let mut fits = true;
+        for ((lower, upper), max_size) in [(self.sizer.size_hint(text), self.max_size)]
+            .into_iter()
+            .chain(limits)
+        {
+            if lower > max_size {
+                return Some(false);
+            }
+            fits &= upper.is_some_and(|upper| upper <= max_size);
+        }
+        fits.then_some(true)
+    }
+
+    /// Check that the text of the given size satisfies every limit, and return the sizes
+    /// measured for each limit if so. Stops measuring at the first limit that is exceeded.
+    fn fit(&self, size: usize, text: &str) -> Result<Option<Vec<usize>>> {
+        if size > self.max_size {
+            return Ok(None);
+        }
+
+        let mut sizes = Vec::with_capacity(self.limits.len() + 1);
+        sizes.push(size);
+        for (sizer, max_size) in &self.limits {
+            if sizer.size_hint(text).0 > *max_size {
+                return Ok(None);
+            }
+            let size = sizer.size(text)?;
+            if size > *max_size {
+                return Ok(None);
+            }
+            sizes.push(size);
+        }
+
+        Ok(Some(sizes))
+    }
+}
+
+/// Create a parser for the language, with a wasm store if the grammar was loaded from wasm.
+fn new_parser(language: &Language) -> Result<Parser> {
+    let mut parser = Parser::new();
+    #[cfg(feature = "wasm")]
+    if language.is_wasm() {
+        crate::wasm::attach_store(&mut parser)?;
     }
+    parser.set_language(language)?;
+    Ok(parser)
+}
+
+/// Parse the text, handing the wasm store back for the next parse on this thread.
+fn parse(language: &Language, text: &[u8]) -> Result<Tree> {
+    let mut parser = new_parser(language)?;
+    let tree = parser.parse(text, None);
+    #[cfg(feature = "wasm")]
+    crate::wasm::release_store(&mut parser);
+    tree.ok_or(Error::Parse)
 }
 
 fn format_node(node: &Node, depth: usize) -> String {
@@ -0,0 +1,92 @@
+use std::{cell::RefCell, sync::OnceLock};
+use tree_sitter::{wasmtime::Engine, Language, Parser, WasmError, WasmStore};
+
+use crate::error::{Error, Result};
+
+/// Shared wasmtime engine. Grammars loaded with one engine can be used by any store created
+/// from the same engine, so every parser gets a store from this one.
+fn engine() -> &'static Engine {
+    static ENGINE: OnceLock<Engine> = OnceLock::new();
+    ENGINE.get_or_init(Engine::default)
+}
+
+/// Load a tree-sitter grammar from a WebAssembly module, e.g. `tree-sitter-mydsl.wasm`.
+///
+/// The grammar runs inside a wasmtime sandbox rather than as native code, so modules from
+/// untrusted sources can be loaded safely. `name` must match the name the grammar was generated
+/// with.
+///
+/// ```no_run
+/// use code_splitter::{load_wasm_language, CharCounter, Splitter};
+/// use std::fs;
+///
+/// let bytes = fs::read("path/to/tree-sitter-mydsl.wasm").unwrap();
+/// let lang = load_wasm_language("mydsl", &bytes).unwrap();
+/// let splitter = Splitter::new(lang, CharCounter).unwrap();
+/// ```
+pub fn load_wasm_language(name: &str, bytes: &[u8]) -> Result<Language> {
+    let load_error = |e: WasmError| Error::LoadGrammar {
+        name: name.to_string(),
+        source: e.into(),
+    };
+
+    let mut store = WasmStore::new(engine().clone()).map_err(load_error)?;
+    let language = store.load_language(name, bytes).map_err(load_error)?;
+    Ok(language)
+}
+
+thread_local! {
+    /// Store reused by the parsers on this thread. A store keeps every grammar it has
+    /// instantiated, so reusing it avoids instantiating the grammar again on every split.
+    static STORE: RefCell<Option<WasmStore>> = const { RefCell::new(None) };
+}
+
+/// Lend the parser this thread's wasm store so that it can run wasm grammars.
+///
+/// Call [`release_store`] once the parser is done with it.
+pub(crate) fn attach_store(parser: &mut Parser) -> Result<()> {
+    let store = match STORE.with_borrow_mut(Option::take) {
+        Some(store) => store,
+        None => WasmStore::new(engine().clone()).map_err(Error::Wasm)?,
+    };
+    parser.set_wasm_store(store)?;
+    Ok(())
+}
+
+/// Take the wasm store back from the parser for the next parser on this thread.
+pub(crate) fn release_store(parser: &mut Parser) {
+    if let Some(store) = parser.take_wasm_store() {
+        STORE.with_borrow_mut(|slot| *slot = Some(store));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CharCounter, LanguageRegistry, Splitter};
+
+    #[test]
+    fn test_load_invalid_module() {
+        let err = load_wasm_language("mydsl", b"not a wasm module").unwrap_err();
+        assert!(matches!(err, Error::LoadGrammar { ref name, .. } if name == "mydsl"));
+    }
+
+    #[test]
+    fn test_split_with_wasm_grammar() {
+        // Built from tests/testdata/tree-sitter-words.rs
+        let bytes = include_bytes!("../tests/testdata/tree-sitter-words.wasm");
+        let mut registry = LanguageRegistry::new();
+        registry.register_wasm("words", bytes).unwrap();
+        let lang = registry.grammar("words").unwrap();
+        assert!(lang.is_wasm());
+
+        let splitter = Splitter::new(lang, CharCounter).unwrap().with_max_size(12);
+        let code = b"hello world\n  foo bar baz\nqux";
+        // The second split reuses the wasm store of the first
+        for _ in 0..2 {
+            let chunks = splitter.split(code).unwrap();
+            let texts: Vec<_> = chunks.iter().map(|chunk| chunk.text(code)).collect();
+            assert_eq!(texts, ["hello world", "foo bar baz", "qux"]);
+        }
+    }
+}
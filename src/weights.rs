@@ -0,0 +1,166 @@
+use std::collections::{HashMap, HashSet};
+use std::str;
+use tree_sitter::Node;
+
+use crate::error::Result;
+
+/// Weights of the nodes of a parsed tree, resolved once from a table of weights per node kind.
+///
+/// A node takes the weight of its kind, or else the weight of its nearest weighted ancestor, so
+/// the innermost weighted kind applies to every byte. Nodes without a weight count fully.
+#[derive(Default)]
+pub(crate) struct NodeWeights {
+    /// Weights of the nodes whose weight isn't 1, keyed by node id.
+    weights: HashMap<usize, f64>,
+    /// Nodes that contain a node with a weighted kind, and so must be sized piece by piece.
+    mixed: HashSet<usize>,
+}
+
+impl NodeWeights {
+    /// Resolve the weights of the nodes in the tree rooted at `root`.
+    pub(crate) fn resolve(table: &HashMap<String, f64>, root: &Node) -> Self {
+        let mut weights = Self::default();
+        weights.visit(table, root, 1.0);
+        weights
+    }
+
+    /// Record the weight of the node and its descendants. Returns whether the subtree contains
+    /// a node with a weighted kind.
+    fn visit(&mut self, table: &HashMap<String, f64>, node: &Node, inherited: f64) -> bool {
+        let own = table.get(node.kind()).copied();
+        let weight = own.unwrap_or(inherited);
+        if weight != 1.0 {
+            self.weights.insert(node.id(), weight);
+        }
+
+        let mut mixed = false;
+        for child in node.children(&mut node.walk()) {
+            mixed |= self.visit(table, &child, weight);
+        }
+        if mixed {
+            self.mixed.insert(node.id());
+        }
+
+        mixed || own.is_some()
+    }
+
+    /// Collect the pieces of text between `start` and `end` within the node, each with the
+    /// weight of the innermost node covering it.
+    pub(crate) fn segments<'a>(
+        &self,
+        node: &Node,
+        start: usize,
+        end: usize,
+        code: &'a [u8],
+        segments: &mut Vec<(&'a str, f64)>,
+    ) -> Result<()> {
+        let weight = self.weights.get(&node.id()).copied().unwrap_or(1.0);
+        if !self.mixed.contains(&node.id()) {
+            segments.push((str::from_utf8(&code[start..end])?, weight));
+            return Ok(());
+        }
+
+        let mut pos = start;
+        for child in node.children(&mut node.walk()) {
+            let child_start = child.start_byte().max(start);
+            let child_end = child.end_byte().min(end);
+            if child_start >= child_end {
+                continue;
+            }
+            if pos < child_start {
+                // Text between the children, such as whitespace, belongs to the node itself
+                segments.push((str::from_utf8(&code[pos..child_start])?, weight));
+            }
+            self.segments(&child, child_start, child_end, code, segments)?;
+            pos = child_end;
+        }
+        if pos < end {
+            segments.push((str::from_utf8(&code[pos..end])?, weight));
+        }
+
+        Ok(())
+    }
+}
+
+/// Sum the weighted sizes of the segments. Text with a positive size counts at least 1, so
+/// that chunks of lightly weighted text aren't mistaken for empty ones.
+pub(crate) fn weighted_sum(sizes: &[usize], weights: &[f64]) -> usize {
+    if sizes.iter().all(|&size| size == 0) {
+        return 0;
+    }
+    let sum = sizes
+        .iter()
+        .zip(weights)
+        .map(|(&size, &weight)| size as f64 * weight)
+        .sum::<f64>();
+    (sum.round() as usize).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tree_sitter::Parser;
+
+    fn segments(table: &[(&str, f64)], code: &str) -> Vec<(String, f64)> {
+        let mut parser = Parser::new();
+        parser.set_language(&tree_sitter_rust::language()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+        let root = tree.root_node();
+
+        let table = table
+            .iter()
+            .map(|&(kind, weight)| (kind.to_string(), weight))
+            .collect();
+        let weights = NodeWeights::resolve(&table, &root);
+        let mut segments = Vec::new();
+        weights
+            .segments(&root, 0, code.len(), code.as_bytes(), &mut segments)
+            .unwrap();
+        segments
+            .into_iter()
+            .map(|(text, weight)| (text.to_string(), weight))
+            .collect()
+    }
+
+    #[test]
+    fn test_unweighted() {
+        let code = "// add\nfn add() {}";
+        assert_eq!(segments(&[], code), vec![(code.to_string(), 1.0)]);
+    }
+
+    #[test]
+    fn test_segments() {
+        let code = "// add\nfn add() { \"x\" }";
+        let segments = segments(&[("line_comment", 0.5), ("string_literal", 2.0)], code);
+        let weighted = segments
+            .iter()
+            .filter(|(_, weight)| *weight != 1.0)
+            .cloned()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            weighted,
+            vec![("// add".to_string(), 0.5), ("\"x\"".to_string(), 2.0)]
+        );
+        // Every byte is covered exactly once
+        let text = segments
+            .into_iter()
+            .map(|(text, _)| text)
+            .collect::<String>();
+        assert_eq!(text, code);
+    }
+
+    #[test]
+    fn test_innermost_weight() {
+        let code = "fn f() { \"x\" }";
+        let segments = segments(&[("function_item", 3.0), ("string_literal", 2.0)], code);
+        assert!(segments.contains(&("\"x\"".to_string(), 2.0)));
+        assert!(segments.contains(&("fn".to_string(), 3.0)));
+    }
+
+    #[test]
+    fn test_weighted_sum() {
+        assert_eq!(weighted_sum(&[4, 6], &[0.5, 2.0]), 14);
+        assert_eq!(weighted_sum(&[3], &[0.0]), 1);
+        assert_eq!(weighted_sum(&[0, 0], &[1.0, 1.0]), 0);
+    }
+}
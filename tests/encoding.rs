@@ -0,0 +1,80 @@
+use code_splitter::{CharCounter, Encoding, Splitter};
+
+fn split(code: &[u8], max_size: usize) -> Vec<code_splitter::Chunk> {
+    let lang = tree_sitter_python::language();
+    let splitter = Splitter::new(lang, CharCounter)
+        .expect("Failed to create python splitter")
+        .with_max_size(max_size);
+    splitter.split(code).expect("Failed to split python code")
+}
+
+#[test]
+fn split_invalid_utf8() {
+    let code = b"def f():\n    return '\xFF'\n\ndef g():\n    return 1\n";
+    let chunks = split(code, 24);
+
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0].range.start_byte, 0);
+    assert_eq!(chunks[1].range.end_byte, code.len() - 1);
+    assert_eq!(chunks[0].text(code), "def f():\n    return '\u{FFFD}'");
+}
+
+#[test]
+fn split_utf16_with_bom() {
+    let text = "def f():\n    return 'é'\n";
+    let code = [0xFF, 0xFE]
+        .into_iter()
+        .chain(text.encode_utf16().flat_map(u16::to_le_bytes))
+        .collect::<Vec<_>>();
+    let chunks = split(&code, 100);
+
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].encoding.name(), "UTF-16LE");
+    assert_eq!(chunks[0].range.start_byte, 2);
+    assert_eq!(chunks[0].range.end_byte, code.len());
+    assert_eq!(chunks[0].size, text.chars().count());
+    assert_eq!(chunks[0].text(&code), text);
+}
+
+#[test]
+fn split_explicit_encoding_skips_bom() {
+    let lang = tree_sitter_python::language();
+    let splitter = Splitter::new(lang, CharCounter)
+        .expect("Failed to create python splitter")
+        .with_encoding(Encoding::for_label(b"utf-16le").unwrap());
+    let code = b"\xEF\xBB\xBFx\0";
+    let chunks = splitter.split(code).expect("Failed to split python code");
+
+    assert_eq!(chunks.len(), 1);
+    assert_eq!(chunks[0].encoding.name(), "UTF-16LE");
+    assert_eq!(chunks[0].range.start_byte, 3);
+    assert_eq!(chunks[0].text(code), "x");
+}
+
+#[test]
+fn split_declared_latin1() {
+    let code = b"# -*- coding: latin-1 -*-\ndef f():\n    return '\xE9'\n";
+    let chunks = split(code, 30);
+
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[1].encoding.name(), "windows-1252");
+    assert_eq!(chunks[1].range.end_byte, code.len() - 1);
+    // Columns count the original bytes, where `é` is a single byte
+    assert_eq!(chunks[1].range.end_point.column, 14);
+    assert_eq!(chunks[1].text(code), "def f():\n    return 'é'");
+}
+
+#[test]
+fn split_truncated_utf8_keeps_every_byte() {
+    // `\xE9` is a truncated sequence, decoded in the same step as the newline after it
+    let code = b"# caf\xE9\nx = 1\n";
+    let chunks = split(code, 7);
+
+    assert_eq!(chunks[0].range.start_byte, 0);
+    assert_eq!(chunks[0].range.end_byte, 6);
+    assert_eq!(chunks[0].text(code), "# caf\u{FFFD}");
+    assert_eq!(chunks[0].size, 6);
+    for pair in chunks.windows(2) {
+        assert!(pair[0].range.end_byte <= pair[1].range.start_byte);
+    }
+}
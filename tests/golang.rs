@@ -1,4 +1,6 @@
-use code_splitter::{CharCounter, Sizer, Splitter, WordCounter};
+use code_splitter::{
+    ByteCounter, CharCounter, CodeTokenCounter, LineCounter, Sizer, Splitter, WordCounter,
+};
 use std::fs;
 
 const TEST_FILE: &str = "tests/testdata/rectangle.go";
@@ -24,11 +26,26 @@ where
     }
 }
 
+#[test]
+fn split_by_bytes() {
+    split_and_show(ByteCounter, 200);
+}
+
 #[test]
 fn split_by_chars() {
     split_and_show(CharCounter, 200);
 }
 
+#[test]
+fn split_by_code_tokens() {
+    split_and_show(CodeTokenCounter, 60);
+}
+
+#[test]
+fn split_by_lines() {
+    split_and_show(LineCounter, 10);
+}
+
 #[cfg(feature = "tokenizers")]
 #[test]
 fn split_by_tokens_huggingface() {
@@ -1,4 +1,6 @@
-use code_splitter::{CharCounter, Sizer, Splitter, WordCounter};
+use code_splitter::{
+    ByteCounter, CharCounter, CodeTokenCounter, LineCounter, Sizer, Splitter, WordCounter,
+};
 use std::fs;
 
 const TEST_FILE: &str = "tests/testdata/rectangle.py";
@@ -24,11 +26,26 @@ where
     }
 }
 
+#[test]
+fn split_by_bytes() {
+    split_and_show(ByteCounter, 200);
+}
+
 #[test]
 fn split_by_chars() {
     split_and_show(CharCounter, 200);
 }
 
+#[test]
+fn split_by_code_tokens() {
+    split_and_show(CodeTokenCounter, 60);
+}
+
+#[test]
+fn split_by_lines() {
+    split_and_show(LineCounter, 10);
+}
+
 #[cfg(feature = "tokenizers")]
 #[test]
 fn split_by_tokens_huggingface() {
@@ -50,3 +67,37 @@ fn split_by_tokens_tiktoken() {
 fn split_by_words() {
     split_and_show(WordCounter, 50);
 }
+
+#[test]
+fn split_with_weights() {
+    let code = read_test_file();
+    let lang = tree_sitter_python::language();
+    let split = |weights: &[(&str, f64)]| {
+        Splitter::new(lang.clone(), CharCounter)
+            .expect("Failed to create python splitter")
+            .with_max_size(200)
+            .with_weights(weights.iter().copied())
+            .split(&code)
+            .expect("Failed to split python code")
+    };
+
+    // Characters add up exactly, so neutral weights don't change the chunks
+    let plain = split(&[]);
+    let neutral = split(&[("comment", 1.0), ("string", 1.0)]);
+    let summary = |chunks: &[code_splitter::Chunk]| {
+        chunks
+            .iter()
+            .map(|chunk| (chunk.range, chunk.size))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(summary(&plain), summary(&neutral));
+
+    // Light comments let more code fit in each chunk
+    let light = split(&[("comment", 0.1), ("string", 0.1)]);
+    assert!(light.len() < plain.len());
+    for chunk in &light {
+        let text = chunk.text(&code);
+        assert!(chunk.size <= 200);
+        assert!(chunk.size <= text.chars().count());
+    }
+}
@@ -1,5 +1,10 @@
-use code_splitter::{CharCounter, Sizer, Splitter, WordCounter};
+use code_splitter::{
+    ByteCounter, CharCounter, Chunk, CodeTokenCounter, LineCounter, Result, Sizer, Splitter,
+    WordCounter,
+};
 use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tree_sitter::Range;
 
 const TEST_FILE: &str = "tests/testdata/rectangle.rs";
 
@@ -7,6 +12,13 @@ fn read_test_file() -> Vec<u8> {
     fs::read(TEST_FILE).expect("Failed to read test file")
 }
 
+fn summarize(chunks: &[Chunk]) -> Vec<(String, Range, Vec<usize>)> {
+    chunks
+        .iter()
+        .map(|chunk| (chunk.subtree.clone(), chunk.range, chunk.sizes.clone()))
+        .collect()
+}
+
 fn split_and_show<T>(sizer: T, max_size: usize)
 where
     T: Sizer,
@@ -24,11 +36,26 @@ where
     }
 }
 
+#[test]
+fn split_by_bytes() {
+    split_and_show(ByteCounter, 200);
+}
+
 #[test]
 fn split_by_chars() {
     split_and_show(CharCounter, 200);
 }
 
+#[test]
+fn split_by_code_tokens() {
+    split_and_show(CodeTokenCounter, 60);
+}
+
+#[test]
+fn split_by_lines() {
+    split_and_show(LineCounter, 10);
+}
+
 #[cfg(feature = "tokenizers")]
 #[test]
 fn split_by_tokens_huggingface() {
@@ -50,3 +77,89 @@ fn split_by_tokens_tiktoken() {
 fn split_by_words() {
     split_and_show(WordCounter, 50);
 }
+
+#[test]
+fn split_with_line_limit() {
+    let code = read_test_file();
+    let lang = tree_sitter_rust::language();
+
+    let splitter = Splitter::new(lang, CharCounter)
+        .expect("Failed to create rust splitter")
+        .with_max_size(500)
+        .with_limit(LineCounter, 10);
+    let chunks = splitter.split(&code).expect("Failed to split rust code");
+
+    for chunk in chunks {
+        assert_eq!(chunk.sizes.len(), 2);
+        assert_eq!(chunk.sizes[0], chunk.size);
+        assert!(chunk.size <= 500);
+        assert!(chunk.sizes[1] <= 10);
+    }
+}
+
+#[test]
+fn split_with_batch_sizer() {
+    /// Counts characters, recording how many batches were sized.
+    #[derive(Default)]
+    struct BatchCounter {
+        batches: AtomicUsize,
+    }
+
+    impl Sizer for BatchCounter {
+        fn size(&self, text: &str) -> Result<usize> {
+            CharCounter.size(text)
+        }
+
+        fn size_batch(&self, texts: &[&str]) -> Result<Vec<usize>> {
+            self.batches.fetch_add(1, Ordering::Relaxed);
+            CharCounter.size_batch(texts)
+        }
+    }
+
+    let code = read_test_file();
+    let lang = tree_sitter_rust::language();
+
+    let sizer = BatchCounter::default();
+    let splitter = Splitter::new(lang.clone(), &sizer)
+        .expect("Failed to create rust splitter")
+        .with_max_size(200);
+    let chunks = splitter.split(&code).expect("Failed to split rust code");
+
+    let expected = Splitter::new(lang, CharCounter)
+        .expect("Failed to create rust splitter")
+        .with_max_size(200)
+        .split(&code)
+        .expect("Failed to split rust code");
+    assert_eq!(summarize(&chunks), summarize(&expected));
+    // The children of every split node are sized in one batch
+    assert!(sizer.batches.load(Ordering::Relaxed) > 0);
+}
+
+#[cfg(feature = "tiktoken-rs")]
+#[test]
+fn split_with_size_hints() {
+    use code_splitter::FnSizer;
+    use tiktoken_rs::cl100k_base;
+
+    let code = read_test_file();
+    let lang = tree_sitter_rust::language();
+    let bpe = cl100k_base().expect("Failed to load tiktoken tokenizer");
+    // Wrapping the sizers in closures hides their size hints
+    let exact_bpe = FnSizer::new(|text: &str| bpe.size(text));
+
+    for max_size in [10, 50, 100, 1000] {
+        let chunks = Splitter::new(lang.clone(), &bpe)
+            .expect("Failed to create rust splitter")
+            .with_max_size(max_size)
+            .with_limit(WordCounter, 40)
+            .split(&code)
+            .expect("Failed to split rust code");
+        let expected = Splitter::new(lang.clone(), &exact_bpe)
+            .expect("Failed to create rust splitter")
+            .with_max_size(max_size)
+            .with_limit(FnSizer::new(|text: &str| WordCounter.size(text)), 40)
+            .split(&code)
+            .expect("Failed to split rust code");
+        assert_eq!(summarize(&chunks), summarize(&expected));
+    }
+}
@@ -0,0 +1,287 @@
+//! A tiny hand-written tree-sitter grammar, used to test loading grammars from WebAssembly.
+//!
+//! It accepts the same language as `source_file: $ => repeat($.word)` with `word: /[a-z]+/`
+//! and whitespace as extras. The tables are written out by hand, so the grammar can be built
+//! with nothing but rustc:
+//!
+//! ```sh
+//! rustc --edition 2021 --target wasm32-unknown-unknown --crate-type cdylib -O -C panic=abort \
+//!     -C relocation-model=pic -C link-arg=--experimental-pic -C link-arg=-shared \
+//!     tests/testdata/tree-sitter-words.rs -o tests/testdata/tree-sitter-words.wasm
+//! ```
+#![no_std]
+
+use core::ffi::c_char;
+
+const LANGUAGE_VERSION: u32 = 14;
+const STATE_COUNT: usize = 6;
+const SYMBOL_COUNT: usize = 4;
+const TOKEN_COUNT: u32 = 2;
+
+const SYM_END: u16 = 0;
+const SYM_WORD: u16 = 1;
+const SYM_SOURCE_FILE: u16 = 2;
+const SYM_SOURCE_FILE_REPEAT1: u16 = 3;
+
+#[repr(C)]
+struct Lexer {
+    lookahead: i32,
+    result_symbol: u16,
+    advance: extern "C" fn(*mut Lexer, bool),
+    mark_end: extern "C" fn(*mut Lexer),
+    get_column: extern "C" fn(*mut Lexer) -> u32,
+    is_at_included_range_start: extern "C" fn(*const Lexer) -> bool,
+    eof: extern "C" fn(*const Lexer) -> bool,
+}
+
+#[repr(C)]
+struct SymbolMetadata {
+    visible: bool,
+    named: bool,
+    supertype: bool,
+}
+
+#[repr(C)]
+struct LexMode {
+    lex_state: u16,
+    external_lex_state: u16,
+}
+
+/// `TSParseActionEntry`: a union of an entry header and the shift/reduce action structs.
+#[repr(C, align(2))]
+struct ParseAction([u8; 8]);
+
+#[repr(C)]
+struct ExternalScanner {
+    states: *const bool,
+    symbol_map: *const u16,
+    create: *const (),
+    destroy: *const (),
+    scan: *const (),
+    serialize: *const (),
+    deserialize: *const (),
+}
+
+#[repr(C)]
+struct Language {
+    version: u32,
+    symbol_count: u32,
+    alias_count: u32,
+    token_count: u32,
+    external_token_count: u32,
+    state_count: u32,
+    large_state_count: u32,
+    production_id_count: u32,
+    field_count: u32,
+    max_alias_sequence_length: u16,
+    parse_table: *const u16,
+    small_parse_table: *const u16,
+    small_parse_table_map: *const u32,
+    parse_actions: *const ParseAction,
+    symbol_names: *const Name,
+    field_names: *const Name,
+    field_map_slices: *const u16,
+    field_map_entries: *const u16,
+    symbol_metadata: *const SymbolMetadata,
+    public_symbol_map: *const u16,
+    alias_map: *const u16,
+    alias_sequences: *const u16,
+    lex_modes: *const LexMode,
+    lex_fn: extern "C" fn(*mut Lexer, u16) -> bool,
+    keyword_lex_fn: *const (),
+    keyword_capture_token: u16,
+    external_scanner: ExternalScanner,
+    primary_state_ids: *const u16,
+}
+
+#[repr(transparent)]
+struct Name(*const c_char);
+
+unsafe impl Sync for Name {}
+unsafe impl Sync for Language {}
+
+const fn entry(count: u8, reusable: bool) -> ParseAction {
+    ParseAction([count, reusable as u8, 0, 0, 0, 0, 0, 0])
+}
+
+const fn shift(state: u16) -> ParseAction {
+    let [lo, hi] = state.to_le_bytes();
+    ParseAction([0, 0, lo, hi, 0, 0, 0, 0])
+}
+
+const fn reduce(symbol: u16, child_count: u8) -> ParseAction {
+    let [lo, hi] = symbol.to_le_bytes();
+    ParseAction([1, child_count, lo, hi, 0, 0, 0, 0])
+}
+
+const ACCEPT: ParseAction = ParseAction([2, 0, 0, 0, 0, 0, 0, 0]);
+const RECOVER: ParseAction = ParseAction([3, 0, 0, 0, 0, 0, 0, 0]);
+
+static SYMBOL_NAMES: [Name; SYMBOL_COUNT] = [
+    Name(c"end".as_ptr()),
+    Name(c"word".as_ptr()),
+    Name(c"source_file".as_ptr()),
+    Name(c"source_file_repeat1".as_ptr()),
+];
+
+static FIELD_NAMES: [Name; 1] = [Name(core::ptr::null())];
+
+static SYMBOL_METADATA: [SymbolMetadata; SYMBOL_COUNT] = [
+    SymbolMetadata {
+        visible: false,
+        named: true,
+        supertype: false,
+    },
+    SymbolMetadata {
+        visible: true,
+        named: true,
+        supertype: false,
+    },
+    SymbolMetadata {
+        visible: true,
+        named: true,
+        supertype: false,
+    },
+    SymbolMetadata {
+        visible: false,
+        named: false,
+        supertype: false,
+    },
+];
+
+static PUBLIC_SYMBOL_MAP: [u16; SYMBOL_COUNT] =
+    [SYM_END, SYM_WORD, SYM_SOURCE_FILE, SYM_SOURCE_FILE_REPEAT1];
+
+// source_file: ε | repeat1
+// repeat1: repeat1 word | word
+#[rustfmt::skip]
+static PARSE_ACTIONS: [ParseAction; 17] = [
+    entry(0, false),
+    entry(1, false), RECOVER,
+    entry(1, true), reduce(SYM_SOURCE_FILE, 0),
+    entry(1, true), shift(3),
+    entry(1, true), ACCEPT,
+    entry(1, true), reduce(SYM_SOURCE_FILE_REPEAT1, 1),
+    entry(1, true), reduce(SYM_SOURCE_FILE, 1),
+    entry(1, true), shift(5),
+    entry(1, true), reduce(SYM_SOURCE_FILE_REPEAT1, 2),
+];
+
+// Terminals map to an index into `PARSE_ACTIONS`, non-terminals to the state to go to.
+static PARSE_TABLE: [[u16; SYMBOL_COUNT]; STATE_COUNT] = [
+    [1, 1, 0, 0],
+    [3, 5, 2, 4],
+    [7, 0, 0, 0],
+    [9, 9, 0, 0],
+    [11, 13, 0, 0],
+    [15, 15, 0, 0],
+];
+
+static LEX_MODES: [LexMode; STATE_COUNT] = [
+    LexMode {
+        lex_state: 0,
+        external_lex_state: 0,
+    },
+    LexMode {
+        lex_state: 0,
+        external_lex_state: 0,
+    },
+    LexMode {
+        lex_state: 0,
+        external_lex_state: 0,
+    },
+    LexMode {
+        lex_state: 0,
+        external_lex_state: 0,
+    },
+    LexMode {
+        lex_state: 0,
+        external_lex_state: 0,
+    },
+    LexMode {
+        lex_state: 0,
+        external_lex_state: 0,
+    },
+];
+
+static PRIMARY_STATE_IDS: [u16; STATE_COUNT] = [0, 1, 2, 3, 4, 5];
+static ALIAS_MAP: [u16; 1] = [0];
+static ALIAS_SEQUENCES: [u16; 2] = [0, 0];
+
+extern "C" fn lex(lexer: *mut Lexer, mut state: u16) -> bool {
+    let lexer = unsafe { &mut *lexer };
+    let mut result = false;
+    loop {
+        let mut skip = false;
+        let c = lexer.lookahead;
+        let lower = (b'a' as i32..=b'z' as i32).contains(&c);
+        match state {
+            0 if (lexer.eof)(lexer) => {
+                lexer.result_symbol = SYM_END;
+                (lexer.mark_end)(lexer);
+                return true;
+            }
+            0 if matches!(c, 0x09..=0x0d | 0x20) => skip = true,
+            0 if lower => state = 1,
+            1 => {
+                result = true;
+                lexer.result_symbol = SYM_WORD;
+                (lexer.mark_end)(lexer);
+                if !lower {
+                    return result;
+                }
+            }
+            _ => return result,
+        }
+        (lexer.advance)(lexer, skip);
+    }
+}
+
+static LANGUAGE: Language = Language {
+    version: LANGUAGE_VERSION,
+    symbol_count: SYMBOL_COUNT as u32,
+    alias_count: 0,
+    token_count: TOKEN_COUNT,
+    external_token_count: 0,
+    state_count: STATE_COUNT as u32,
+    large_state_count: STATE_COUNT as u32,
+    production_id_count: 1,
+    field_count: 0,
+    max_alias_sequence_length: 2,
+    parse_table: PARSE_TABLE.as_ptr().cast(),
+    small_parse_table: core::ptr::null(),
+    small_parse_table_map: core::ptr::null(),
+    parse_actions: PARSE_ACTIONS.as_ptr(),
+    symbol_names: SYMBOL_NAMES.as_ptr(),
+    field_names: FIELD_NAMES.as_ptr(),
+    field_map_slices: core::ptr::null(),
+    field_map_entries: core::ptr::null(),
+    symbol_metadata: SYMBOL_METADATA.as_ptr(),
+    public_symbol_map: PUBLIC_SYMBOL_MAP.as_ptr(),
+    alias_map: ALIAS_MAP.as_ptr(),
+    alias_sequences: ALIAS_SEQUENCES.as_ptr(),
+    lex_modes: LEX_MODES.as_ptr(),
+    lex_fn: lex,
+    keyword_lex_fn: core::ptr::null(),
+    keyword_capture_token: 0,
+    external_scanner: ExternalScanner {
+        states: core::ptr::null(),
+        symbol_map: core::ptr::null(),
+        create: core::ptr::null(),
+        destroy: core::ptr::null(),
+        scan: core::ptr::null(),
+        serialize: core::ptr::null(),
+        deserialize: core::ptr::null(),
+    },
+    primary_state_ids: PRIMARY_STATE_IDS.as_ptr(),
+};
+
+#[no_mangle]
+pub extern "C" fn tree_sitter_words() -> *const () {
+    &LANGUAGE as *const Language as *const ()
+}
+
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo) -> ! {
+    loop {}
+}
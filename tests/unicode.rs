@@ -0,0 +1,64 @@
+use code_splitter::{CharCounter, DisplayWidthCounter, GraphemeCounter, Sizer, Splitter};
+use std::fs;
+
+const TEST_FILE: &str = "tests/testdata/unicode.md";
+
+fn read_test_file() -> Vec<u8> {
+    fs::read(TEST_FILE).expect("Failed to read test file")
+}
+
+fn split<T>(sizer: T, max_size: usize) -> Vec<code_splitter::Chunk>
+where
+    T: Sizer,
+{
+    let code = read_test_file();
+    let lang = tree_sitter_md::language();
+
+    let splitter = Splitter::new(lang, sizer)
+        .expect("Failed to create markdown splitter")
+        .with_max_size(max_size);
+    splitter.split(&code).expect("Failed to split markdown")
+}
+
+#[test]
+fn split_by_graphemes() {
+    let code = read_test_file();
+    let chunks = split(GraphemeCounter, 60);
+
+    for chunk in &chunks {
+        let text = chunk.text(&code);
+        assert!(chunk.size <= 60);
+        // Emoji sequences and combining marks count once, so never more than code points
+        assert!(chunk.size <= text.chars().count());
+    }
+    assert!(chunks.iter().any(|chunk| {
+        let text = chunk.text(&code);
+        chunk.size < text.chars().count()
+    }));
+}
+
+#[test]
+fn split_by_display_width() {
+    let chunks = split(DisplayWidthCounter, 80);
+
+    for chunk in &chunks {
+        assert!(chunk.size <= 80);
+    }
+    // Wide characters take two columns, so more chunks are needed than by characters
+    assert!(chunks.len() > split(CharCounter, 80).len());
+}
+
+#[test]
+fn size_cjk_and_emoji() {
+    let cases = [
+        ("代码分割器", 5, 10),
+        ("코드 분할기", 6, 11),
+        ("👨‍👩‍👧‍👦", 1, 2),
+        ("🇯🇵🇨🇳", 2, 4),
+        ("cafe\u{301}", 4, 4),
+    ];
+    for (text, graphemes, width) in cases {
+        assert_eq!(GraphemeCounter.size(text).unwrap(), graphemes, "{text}");
+        assert_eq!(DisplayWidthCounter.size(text).unwrap(), width, "{text}");
+    }
+}